@@ -0,0 +1,276 @@
+use aes_gcm::Aes256Gcm;
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use chacha20poly1305::{
+    aead::{generic_array::GenericArray, Aead, NewAead},
+    XChaCha20Poly1305,
+};
+use rand_core::{CryptoRng, RngCore};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use zeroize::Zeroize;
+
+use crate::error::CipheringError;
+
+const KEY_LENGTH: usize = 32;
+
+/// Authenticated cipher used to encrypt the content of a referenced media item.
+#[derive(Default, Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum CipherAlgorithm {
+    /// AES-256 in Galois/Counter Mode with a 96 bit IV, as in the Web Crypto API.
+    #[default]
+    #[serde(rename = "AES-GCM")]
+    Aes256Gcm,
+    /// XChaCha20-Poly1305 with a 192 bit nonce.
+    #[serde(rename = "XChaCha20-Poly1305")]
+    XChaCha20Poly1305,
+}
+
+impl CipherAlgorithm {
+    fn iv_length(&self) -> usize {
+        match self {
+            CipherAlgorithm::Aes256Gcm => 12,
+            CipherAlgorithm::XChaCha20Poly1305 => 24,
+        }
+    }
+}
+
+/// Key and IV of a supported cipher suite.
+#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CipheringParameters {
+    /// Hex encoded initialization vector, or nonce, of the length required by the cipher.
+    /// A `0x` prefix is accepted.
+    pub iv: String,
+    /// Hex encoded 256 bit content encryption key. A `0x` prefix is accepted.
+    pub key: String,
+}
+
+/// Encryption information of a referenced media item: how to decrypt the content behind its link.
+/// Serialized as `{"algorithm": .., "parameters": {"iv": .., "key": ..}}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Ciphering {
+    /// AES-256-GCM, serialized as algorithm "AES-GCM".
+    Aes256Gcm(CipheringParameters),
+    /// XChaCha20-Poly1305.
+    XChaCha20Poly1305(CipheringParameters),
+    /// Encryption information of an unsupported algorithm, e.g. from older messages, kept as is.
+    Unknown(Value),
+}
+
+impl Ciphering {
+    /// Creates the encryption information of a supported cipher suite.
+    pub fn new(algorithm: CipherAlgorithm, parameters: CipheringParameters) -> Self {
+        match algorithm {
+            CipherAlgorithm::Aes256Gcm => Ciphering::Aes256Gcm(parameters),
+            CipherAlgorithm::XChaCha20Poly1305 => Ciphering::XChaCha20Poly1305(parameters),
+        }
+    }
+
+    /// Cipher suite, or `None` for `Unknown`.
+    pub fn algorithm(&self) -> Option<CipherAlgorithm> {
+        self.supported().map(|(algorithm, _)| algorithm)
+    }
+
+    /// Parameters of the cipher suite, or `None` for `Unknown`.
+    pub fn parameters(&self) -> Option<&CipheringParameters> {
+        self.supported().map(|(_, parameters)| parameters)
+    }
+
+    fn supported(&self) -> Option<(CipherAlgorithm, &CipheringParameters)> {
+        match self {
+            Ciphering::Aes256Gcm(parameters) => Some((CipherAlgorithm::Aes256Gcm, parameters)),
+            Ciphering::XChaCha20Poly1305(parameters) => {
+                Some((CipherAlgorithm::XChaCha20Poly1305, parameters))
+            }
+            Ciphering::Unknown(_) => None,
+        }
+    }
+
+    /// Value of the `algorithm` field of `Unknown` encryption information.
+    fn unknown_algorithm(&self) -> Option<&str> {
+        match self {
+            Ciphering::Unknown(value) => value.get("algorithm").and_then(Value::as_str),
+            _ => None,
+        }
+    }
+
+    /// Checks the parameters of a supported cipher suite: the IV and the key must be hex encoded with
+    /// the lengths required by the cipher, so that `decrypt_content` can use them. Unknown algorithms are accepted as they are,
+    /// but not malformed encryption information naming a supported algorithm.
+    pub fn validate(&self) -> Result<(), CipheringError> {
+        match self.supported() {
+            Some((algorithm, parameters)) => {
+                decode_iv(algorithm, &parameters.iv)?;
+                decode_key(&parameters.key)?.zeroize();
+                Ok(())
+            }
+            None if self.unknown_algorithm().is_some_and(|algorithm| {
+                CipherAlgorithm::deserialize(Value::from(algorithm)).is_ok()
+            }) =>
+            {
+                Err(CipheringError::InvalidParameter("parameters"))
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+/// Classifies encryption information: supported cipher suites with well-formed parameters become typed,
+/// anything else is kept as `Unknown`.
+impl From<Value> for Ciphering {
+    fn from(value: Value) -> Self {
+        let algorithm = value
+            .get("algorithm")
+            .and_then(|algorithm| CipherAlgorithm::deserialize(algorithm).ok());
+        let parameters = value
+            .get("parameters")
+            .and_then(|parameters| CipheringParameters::deserialize(parameters).ok());
+        match (algorithm, parameters) {
+            (Some(algorithm), Some(parameters)) => Ciphering::new(algorithm, parameters),
+            _ => Ciphering::Unknown(value),
+        }
+    }
+}
+
+impl Serialize for Ciphering {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct Supported<'a> {
+            algorithm: CipherAlgorithm,
+            parameters: &'a CipheringParameters,
+        }
+
+        match self {
+            Ciphering::Aes256Gcm(parameters) => Supported {
+                algorithm: CipherAlgorithm::Aes256Gcm,
+                parameters,
+            }
+            .serialize(serializer),
+            Ciphering::XChaCha20Poly1305(parameters) => Supported {
+                algorithm: CipherAlgorithm::XChaCha20Poly1305,
+                parameters,
+            }
+            .serialize(serializer),
+            Ciphering::Unknown(value) => value.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Ciphering {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Value::deserialize(deserializer).map(Ciphering::from)
+    }
+}
+
+/// Content encrypted by `encrypt_content`, to be uploaded to the link of a referenced media item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncryptedContent {
+    /// Encrypted content followed by the authentication tag.
+    pub ciphertext: Vec<u8>,
+    /// Encryption information for the `ciphering` of the media item.
+    pub ciphering: Ciphering,
+}
+
+/// Encrypts `content` with a fresh random key and IV.
+/// The ciphertext includes the authentication tag, so `decrypt_content` detects any modification.
+pub fn encrypt_content(
+    algorithm: CipherAlgorithm,
+    content: &[u8],
+    rng: &mut (impl CryptoRng + RngCore),
+) -> Result<EncryptedContent, CipheringError> {
+    let mut key = [0u8; KEY_LENGTH];
+    rng.fill_bytes(&mut key);
+    let mut iv = vec![0u8; algorithm.iv_length()];
+    rng.fill_bytes(&mut iv);
+
+    let ciphertext = match algorithm {
+        CipherAlgorithm::Aes256Gcm => Aes256Gcm::new(GenericArray::from_slice(&key))
+            .encrypt(GenericArray::from_slice(&iv), content),
+        CipherAlgorithm::XChaCha20Poly1305 => {
+            XChaCha20Poly1305::new(GenericArray::from_slice(&key))
+                .encrypt(GenericArray::from_slice(&iv), content)
+        }
+    }
+    .map_err(|_| CipheringError::EncryptionFailed);
+
+    let ciphering = Ciphering::new(
+        algorithm,
+        CipheringParameters {
+            iv: encode_hex(&iv),
+            key: encode_hex(&key),
+        },
+    );
+    key.zeroize();
+    Ok(EncryptedContent {
+        ciphertext: ciphertext?,
+        ciphering,
+    })
+}
+
+/// Decrypts content encrypted with `ciphering`, e.g. downloaded from the link of a referenced media item.
+/// The key in the parameters must be the hex encoded content encryption key.
+pub fn decrypt_content(
+    ciphertext: &[u8],
+    ciphering: &Ciphering,
+) -> Result<Vec<u8>, CipheringError> {
+    let (algorithm, parameters) = ciphering.supported().ok_or_else(|| {
+        CipheringError::UnsupportedAlgorithm(
+            ciphering
+                .unknown_algorithm()
+                .unwrap_or_default()
+                .to_string(),
+        )
+    })?;
+    let iv = decode_iv(algorithm, &parameters.iv)?;
+    let mut key = decode_key(&parameters.key)?;
+
+    let content = match algorithm {
+        CipherAlgorithm::Aes256Gcm => Aes256Gcm::new(GenericArray::from_slice(&key))
+            .decrypt(GenericArray::from_slice(&iv), ciphertext),
+        CipherAlgorithm::XChaCha20Poly1305 => {
+            XChaCha20Poly1305::new(GenericArray::from_slice(&key))
+                .decrypt(GenericArray::from_slice(&iv), ciphertext)
+        }
+    };
+    key.zeroize();
+    content.map_err(|_| CipheringError::DecryptionFailed)
+}
+
+fn decode_iv(algorithm: CipherAlgorithm, iv: &str) -> Result<Vec<u8>, CipheringError> {
+    decode_hex(iv)
+        .filter(|iv| iv.len() == algorithm.iv_length())
+        .ok_or(CipheringError::InvalidParameter("iv"))
+}
+
+/// Both supported ciphers take a 256 bit key.
+fn decode_key(key: &str) -> Result<Vec<u8>, CipheringError> {
+    decode_hex(key)
+        .filter(|key| key.len() == KEY_LENGTH)
+        .ok_or(CipheringError::InvalidParameter("key"))
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    bytes
+        .iter()
+        .flat_map(|b| [DIGITS[(b >> 4) as usize], DIGITS[(b & 0xf) as usize]])
+        .map(char::from)
+        .collect()
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    let hex = hex.strip_prefix("0x").unwrap_or(hex);
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            let high = (pair[0] as char).to_digit(16)?;
+            let low = (pair[1] as char).to_digit(16)?;
+            Some((high * 16 + low) as u8)
+        })
+        .collect()
+}
@@ -0,0 +1,45 @@
+use core::fmt::Debug;
+
+/// Source of the current time for message timestamps.
+/// Builders consult their clock when `created_time(None)` asks for the current time.
+pub trait Clock: Debug {
+    /// Current time in seconds since the Unix epoch.
+    fn now(&self) -> u64;
+
+    /// Current time in milliseconds since the Unix epoch, used for time-ordered IDs.
+    fn now_millis(&self) -> u64 {
+        self.now() * 1000
+    }
+}
+
+/// Clock reading the system time. Used by builders that have no clock set.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now(&self) -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default()
+    }
+
+    fn now_millis(&self) -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or_default()
+    }
+}
+
+/// Clock that always returns the same time, for reproducible messages in tests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedClock(pub u64);
+
+impl Clock for FixedClock {
+    fn now(&self) -> u64 {
+        self.0
+    }
+}
@@ -1,3 +1,21 @@
+/// Header names defined by DIDComm messaging and the message types of this crate,
+/// which can't be set as custom headers in any letter case.
+pub(crate) const RESERVED_HEADERS: &[&str] = &[
+    "id",
+    "type",
+    "typ",
+    "body",
+    "from",
+    "to",
+    "thid",
+    "pthid",
+    "created_time",
+    "expires_time",
+    "attachments",
+    "from_prior",
+    "lang",
+];
+
 #[macro_export]
 macro_rules! impl_common_builder {
     ($builder:ty) => {
@@ -13,60 +31,189 @@ macro_rules! impl_common_builder {
                 self
             }
 
-            /// Sets the `to` header of the message.
+            /// Adds a recipient to the `to` header of the message.
+            /// Recipients that were already added are skipped, keeping the order in which they were first added.
+            /// Recipients must be DIDs, optionally with a fragment; `build` rejects other values.
             pub fn to(mut self, value: String) -> Self {
-                if let Some(ref mut self_to) = self.to {
-                    self_to.push(value);
-                } else {
-                    self.to = Some(vec![value]);
+                let to = self.to.get_or_insert_with(Vec::new);
+                if !to.contains(&value) {
+                    to.push(value);
                 }
                 self
             }
 
-            /// Sets the `from` header of the message.
+            /// Adds several recipients to the `to` header of the message, see `to`.
+            pub fn to_many(self, values: impl IntoIterator<Item = String>) -> Self {
+                values
+                    .into_iter()
+                    .fold(self, |builder, value| builder.to(value))
+            }
+
+            /// Sets the `from` header of the message. Like recipients, the sender must be a DID.
             pub fn from(mut self, from: String) -> Self {
                 self.from = Some(from);
                 self
             }
 
+            /// Sets the `thid` header of the message: the ID of the thread the message belongs to.
+            /// Replies carry the `thid` of the message they answer.
+            pub fn thid(mut self, thid: String) -> Self {
+                self.thid = Some(thid);
+                self
+            }
+
+            /// Sets the `pthid` header of the message: the ID of the parent thread,
+            /// for messages that start a sub-thread.
+            pub fn pthid(mut self, pthid: String) -> Self {
+                self.pthid = Some(pthid);
+                self
+            }
+
+            /// Adds a custom top-level header to the message, e.g. application metadata.
+            /// Replaces any custom header with the same name. Reserved DIDComm header names,
+            /// such as `id`, `type`, `to` or `thid`, are rejected by `build`.
+            pub fn header(mut self, name: String, value: serde_json::Value) -> Self {
+                self.headers.insert(name, value);
+                self
+            }
+
             /// Sets the `created_time` header of the message.
-            /// If `created_time` is `None`, it will be set to the current time.
+            /// If `created_time` is `None`, it will be set to the current time of the builder's clock
+            /// when the message is built.
             /// The value is in seconds since the Unix epoch.
             pub fn created_time(mut self, created_time: Option<u64>) -> Self {
-                if let Some(created_time) = created_time {
-                    self.created_time = Some(created_time);
-                } else {
-                    self.created_time = Some(chrono::Utc::now().timestamp() as u64);
+                self.created_time = created_time;
+                self.created_time_now = created_time.is_none();
+                self
+            }
+
+            /// Sets the clock used for the current time.
+            /// Without a clock, the system clock is used if the `std` feature is enabled.
+            pub fn clock(mut self, clock: impl Clock + Send + Sync + 'static) -> Self {
+                self.clock = Some(Arc::new(clock));
+                self
+            }
+
+            /// Current time of the builder's clock.
+            fn now(&self) -> Result<u64, MessageBuilderError> {
+                if let Some(clock) = &self.clock {
+                    return Ok(clock.now());
                 }
+                #[cfg(feature = "std")]
+                return Ok($crate::clock::SystemClock.now());
+                #[cfg(not(feature = "std"))]
+                return Err(MessageBuilderError::MissingClock);
+            }
+
+            /// Sets the generator for the message ID and other generated IDs, such as media item `@id`s.
+            /// Without a generator, random UUIDv4 IDs are used.
+            pub fn id_generator(
+                mut self,
+                id_generator: impl IdGenerator + Send + Sync + 'static,
+            ) -> Self {
+                self.id_generator = Some(Arc::new(id_generator));
                 self
             }
 
+            /// Next ID of the builder's ID generator.
+            fn next_id(&self) -> String {
+                match &self.id_generator {
+                    Some(id_generator) => id_generator.next_id(),
+                    None => $crate::id_generator::RandomIdGenerator.next_id(),
+                }
+            }
+
             /// Sets the `expires_time` header of the message.
             /// The value is in seconds since the Unix epoch.
+            /// `build` rejects an `expires_time` that is not after `created_time` (or the current time
+            /// without `created_time`), or that is further than the maximum TTL from it.
             pub fn expires_time(mut self, expires_time: u64) -> Self {
                 self.expires_time = Some(expires_time);
                 self
             }
 
+            /// Sets the maximum TTL of the message in seconds, see `expires_time`.
+            /// Defaults to [`DEFAULT_MAX_TTL`]($crate::DEFAULT_MAX_TTL).
+            pub fn max_ttl(mut self, max_ttl: u64) -> Self {
+                self.max_ttl = Some(max_ttl);
+                self
+            }
+
+            /// Checks `expires_time` against the creation time of the message and the maximum TTL.
+            fn validate_expires_time(
+                &self,
+                expires_time: u64,
+                created_time: Option<u64>,
+            ) -> Result<(), MessageBuilderError> {
+                let start = match created_time {
+                    Some(created_time) if expires_time <= created_time => {
+                        return Err(MessageBuilderError::ExpiresBeforeCreated(expires_time));
+                    }
+                    Some(created_time) => created_time,
+                    None => {
+                        let now = self.now()?;
+                        if expires_time <= now {
+                            return Err(MessageBuilderError::ExpiresInPast(expires_time));
+                        }
+                        now
+                    }
+                };
+                let max_ttl = self.max_ttl.unwrap_or($crate::DEFAULT_MAX_TTL);
+                if expires_time.saturating_sub(start) > max_ttl {
+                    return Err(MessageBuilderError::ExpiresTooLate(expires_time));
+                }
+                Ok(())
+            }
+
             fn add_common_to_builder(
                 &self,
                 mut didcomm_msg_builder: MessageBuilder,
             ) -> Result<MessageBuilder, MessageBuilderError> {
                 if let Some(to) = self.to.clone() {
                     for value in to {
+                        if !$crate::did_validation::is_valid_did(&value) {
+                            return Err(MessageBuilderError::InvalidDid(value));
+                        }
                         didcomm_msg_builder = didcomm_msg_builder.to(value);
                     }
                 }
 
                 if let Some(from) = self.from.clone() {
+                    if !$crate::did_validation::is_valid_did(&from) {
+                        return Err(MessageBuilderError::InvalidDid(from));
+                    }
                     didcomm_msg_builder = didcomm_msg_builder.from(from);
                 }
 
-                if let Some(created_time) = self.created_time {
+                if let Some(thid) = self.thid.clone() {
+                    didcomm_msg_builder = didcomm_msg_builder.thid(thid);
+                }
+
+                if let Some(pthid) = self.pthid.clone() {
+                    didcomm_msg_builder = didcomm_msg_builder.pthid(pthid);
+                }
+
+                for (name, value) in self.headers.clone() {
+                    if $crate::common_builder_macro::RESERVED_HEADERS
+                        .iter()
+                        .any(|reserved| reserved.eq_ignore_ascii_case(&name))
+                    {
+                        return Err(MessageBuilderError::ReservedHeader(name));
+                    }
+                    didcomm_msg_builder = didcomm_msg_builder.header(name, value);
+                }
+
+                let created_time = if self.created_time_now {
+                    Some(self.now()?)
+                } else {
+                    self.created_time
+                };
+                if let Some(created_time) = created_time {
                     didcomm_msg_builder = didcomm_msg_builder.created_time(created_time);
                 }
 
                 if let Some(expires_time) = self.expires_time {
+                    self.validate_expires_time(expires_time, created_time)?;
                     didcomm_msg_builder = didcomm_msg_builder.expires_time(expires_time);
                 }
 
@@ -0,0 +1,100 @@
+use alloc::{
+    boxed::Box,
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use async_trait::async_trait;
+use didcomm::{
+    did::{DIDDoc, DIDResolver},
+    error::ErrorKind,
+};
+use serde_json::Value;
+
+use crate::error::ResolverError;
+
+/// Resolves DIDs into their DID documents.
+/// Used to find the keys of the `to` and `from` DIDs of a message.
+#[async_trait(?Send)]
+pub trait DidResolver {
+    /// Resolves a DID. Returns `None` if the DID is unknown.
+    async fn resolve(&self, did: &str) -> Result<Option<DIDDoc>, ResolverError>;
+}
+
+/// DID resolver over a static set of DID documents, for tests and offline tools.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryDidResolver {
+    did_docs: BTreeMap<String, DIDDoc>,
+}
+
+impl InMemoryDidResolver {
+    /// Creates an empty resolver.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a DID document, replacing any document with the same ID.
+    pub fn add_did_doc(mut self, did_doc: DIDDoc) -> Self {
+        self.did_docs.insert(did_doc.id.clone(), did_doc);
+        self
+    }
+
+    /// Creates a resolver from a JSON DID document or a JSON array of DID documents.
+    /// Missing `keyAgreement`, `authentication`, `verificationMethod` and `service` sets are treated as empty.
+    pub fn from_json(json: &str) -> Result<Self, ResolverError> {
+        let value: Value = serde_json::from_str(json)
+            .map_err(|e| ResolverError::InvalidDidDocument(e.to_string()))?;
+        let values = match value {
+            Value::Array(values) => values,
+            value => vec![value],
+        };
+
+        values
+            .into_iter()
+            .map(parse_did_doc)
+            .collect::<Result<Vec<_>, ResolverError>>()
+            .map(|did_docs| did_docs.into_iter().fold(Self::new(), Self::add_did_doc))
+    }
+}
+
+impl From<Vec<DIDDoc>> for InMemoryDidResolver {
+    fn from(did_docs: Vec<DIDDoc>) -> Self {
+        did_docs.into_iter().fold(Self::new(), Self::add_did_doc)
+    }
+}
+
+#[async_trait(?Send)]
+impl DidResolver for InMemoryDidResolver {
+    async fn resolve(&self, did: &str) -> Result<Option<DIDDoc>, ResolverError> {
+        Ok(self.did_docs.get(did).cloned())
+    }
+}
+
+fn parse_did_doc(mut value: Value) -> Result<DIDDoc, ResolverError> {
+    if let Value::Object(ref mut fields) = value {
+        for field in [
+            "keyAgreement",
+            "authentication",
+            "verificationMethod",
+            "service",
+        ] {
+            fields
+                .entry(field)
+                .or_insert_with(|| Value::Array(Vec::new()));
+        }
+    }
+    serde_json::from_value(value).map_err(|e| ResolverError::InvalidDidDocument(e.to_string()))
+}
+
+/// Exposes a [`DidResolver`] to the didcomm crate.
+pub(crate) struct DidResolverAdapter<'a>(pub(crate) &'a dyn DidResolver);
+
+#[async_trait(?Send)]
+impl DIDResolver for DidResolverAdapter<'_> {
+    async fn resolve(&self, did: &str) -> didcomm::error::Result<Option<DIDDoc>> {
+        self.0
+            .resolve(did)
+            .await
+            .map_err(|e| didcomm::error::Error::msg(ErrorKind::DIDNotResolved, e.to_string()))
+    }
+}
@@ -0,0 +1,225 @@
+use alloc::vec::Vec;
+use blake2::{Blake2b512, Digest};
+
+/// SS58 address prefix of the KILT network.
+const KILT_SS58_PREFIX: u8 = 38;
+
+/// Checks that `did` is a DID with an optional fragment, following the
+/// [DID syntax](https://www.w3.org/TR/did-core/#did-syntax), e.g. "did:example:alice#key-1".
+/// DIDs of the `kilt`, `key`, `web` and `peer` methods are also checked against their method specification;
+/// other methods only need to follow the generic syntax.
+pub(crate) fn is_valid_did(did: &str) -> bool {
+    let (did, fragment) = match did.split_once('#') {
+        Some((did, fragment)) => (did, Some(fragment)),
+        None => (did, None),
+    };
+    if fragment.is_some_and(|fragment| !is_valid_fragment(fragment)) {
+        return false;
+    }
+
+    // did = "did:" method-name ":" method-specific-id
+    let Some(rest) = did.strip_prefix("did:") else {
+        return false;
+    };
+    let Some((method, id)) = rest.split_once(':') else {
+        return false;
+    };
+    if method.is_empty()
+        || !method
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+    {
+        return false;
+    }
+    // method-specific-id = *( *idchar ":" ) 1*idchar
+    if id.is_empty() || id.ends_with(':') || !id.split(':').all(is_idchars) {
+        return false;
+    }
+
+    match method {
+        "kilt" => is_valid_kilt_id(id),
+        "key" => is_multibase_key(id),
+        "web" => is_valid_web_id(id),
+        "peer" => is_valid_peer_id(id),
+        _ => true,
+    }
+}
+
+/// `did:kilt:<address>` or `did:kilt:light:<key type><address>[:<details>]`,
+/// where the address is a KILT SS58 address.
+fn is_valid_kilt_id(id: &str) -> bool {
+    let Some(light) = id.strip_prefix("light:") else {
+        return is_kilt_address(id);
+    };
+    let (light, details) = match light.split_once(':') {
+        Some((light, details)) => (light, Some(details)),
+        None => (light, None),
+    };
+    if details.is_some_and(|details| decode_multibase(details).is_none()) {
+        return false;
+    }
+    light.len() > 2
+        && light.as_bytes()[..2].iter().all(u8::is_ascii_digit)
+        && is_kilt_address(&light[2..])
+}
+
+fn is_kilt_address(address: &str) -> bool {
+    let Ok(bytes) = bs58::decode(address).into_vec() else {
+        return false;
+    };
+    // prefix (1 byte) + public key (32 bytes) + checksum (2 bytes)
+    if bytes.len() != 35 || bytes[0] != KILT_SS58_PREFIX {
+        return false;
+    }
+    let checksum = Blake2b512::new()
+        .chain_update(b"SS58PRE")
+        .chain_update(&bytes[..33])
+        .finalize();
+    checksum[..2] == bytes[33..]
+}
+
+/// `did:web:<domain>[%3A<port>][:<path>]*`
+fn is_valid_web_id(id: &str) -> bool {
+    let mut segments = id.split(':');
+    let Some(host) = segments.next() else {
+        return false;
+    };
+    let (domain, port) = match host.find("%3A").or_else(|| host.find("%3a")) {
+        Some(index) => (&host[..index], Some(&host[index + 3..])),
+        None => (host, None),
+    };
+    if port.is_some_and(|port| port.is_empty() || !port.bytes().all(|b| b.is_ascii_digit())) {
+        return false;
+    }
+    let is_label = |label: &str| {
+        (1..=63).contains(&label.len())
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+    };
+    domain.split('.').all(is_label) && segments.all(|segment| !segment.is_empty())
+}
+
+/// `did:peer:<numalgo><encoded>` for the numalgos 0 to 4 of the
+/// [did:peer method](https://identity.foundation/peer-did-method-spec/).
+fn is_valid_peer_id(id: &str) -> bool {
+    let Some(numalgo) = id.chars().next() else {
+        return false;
+    };
+    let encoded = &id[1..];
+    match numalgo {
+        '0' => is_multibase_key(encoded),
+        '1' | '3' => decode_multibase(encoded).is_some(),
+        '2' => {
+            let mut elements = encoded.split('.');
+            // The numalgo is followed by a "." separated list of elements.
+            elements.next() == Some("")
+                && elements.clone().next().is_some()
+                && elements.all(is_valid_peer_element)
+        }
+        '4' => match encoded.split_once(':') {
+            Some((hash, document)) => {
+                decode_multibase(hash).is_some() && decode_multibase(document).is_some()
+            }
+            None => decode_multibase(encoded).is_some(),
+        },
+        _ => false,
+    }
+}
+
+/// Element of a numalgo 2 peer DID: a purpose code followed by a key or, for services, base64url encoded JSON.
+fn is_valid_peer_element(element: &str) -> bool {
+    let Some(purpose) = element.chars().next() else {
+        return false;
+    };
+    let value = &element[1..];
+    match purpose {
+        'A' | 'E' | 'V' | 'I' | 'D' => is_multibase_key(value),
+        'S' => {
+            !value.is_empty()
+                && value
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'='))
+        }
+        _ => false,
+    }
+}
+
+/// Base58btc multibase encoded public key with its multicodec prefix, as in `did:key`.
+fn is_multibase_key(value: &str) -> bool {
+    let Some(bytes) = decode_multibase(value) else {
+        return false;
+    };
+    match bytes.as_slice() {
+        // ed25519-pub, x25519-pub
+        [0xed, 0x01, key @ ..] | [0xec, 0x01, key @ ..] => key.len() == 32,
+        // secp256k1-pub, p256-pub (compressed)
+        [0xe7, 0x01, key @ ..] | [0x80, 0x24, key @ ..] => key.len() == 33,
+        _ => false,
+    }
+}
+
+/// Decodes a base58btc multibase value, i.e. "z" followed by base58.
+fn decode_multibase(value: &str) -> Option<Vec<u8>> {
+    let encoded = value.strip_prefix('z')?;
+    if encoded.is_empty() {
+        return None;
+    }
+    bs58::decode(encoded).into_vec().ok()
+}
+
+/// idchar = ALPHA / DIGIT / "." / "-" / "_" / pct-encoded
+fn is_idchars(value: &str) -> bool {
+    is_pct_encoded_with(value, |b| {
+        b.is_ascii_alphanumeric() || matches!(b, b'.' | b'-' | b'_')
+    })
+}
+
+/// fragment = *( pchar / "/" / "?" )
+fn is_valid_fragment(fragment: &str) -> bool {
+    is_pct_encoded_with(fragment, |b| {
+        b.is_ascii_alphanumeric()
+            || matches!(
+                b,
+                b'-' | b'.'
+                    | b'_'
+                    | b'~'
+                    | b'!'
+                    | b'$'
+                    | b'&'
+                    | b'\''
+                    | b'('
+                    | b')'
+                    | b'*'
+                    | b'+'
+                    | b','
+                    | b';'
+                    | b'='
+                    | b':'
+                    | b'@'
+                    | b'/'
+                    | b'?'
+            )
+    })
+}
+
+/// Checks that every byte of `value` is allowed by `is_allowed` or part of a "%" HEXDIG HEXDIG escape.
+fn is_pct_encoded_with(value: &str, is_allowed: impl Fn(u8) -> bool) -> bool {
+    let mut bytes = value.bytes();
+    while let Some(b) = bytes.next() {
+        if b == b'%' {
+            let escape = [bytes.next(), bytes.next()];
+            if !escape
+                .iter()
+                .all(|b| b.is_some_and(|b| b.is_ascii_hexdigit()))
+            {
+                return false;
+            }
+        } else if !is_allowed(b) {
+            return false;
+        }
+    }
+    true
+}
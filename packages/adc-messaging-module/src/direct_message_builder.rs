@@ -1,11 +1,19 @@
-use alloc::string::ToString;
-use alloc::{string::String, vec::Vec};
+use alloc::{collections::BTreeMap, string::String, sync::Arc, vec::Vec};
 use didcomm::Message;
 use didcomm::MessageBuilder;
-use uuid::Uuid;
+use serde_json::Value;
 
+use crate::clock::Clock;
 use crate::error::MessageBuilderError;
+use crate::id_generator::IdGenerator;
 use crate::impl_common_builder;
+use crate::language_tag::is_valid_language_tag;
+
+/// Message type URI of a [Basic Message](https://didcomm.org/basicmessage/2.0/).
+pub const DIRECT_MESSAGE_TYPE: &str = "https://didcomm.org/basicmessage/2.0/message";
+
+/// Header holding the language of a direct message.
+pub(crate) const LANG_HEADER: &str = "lang";
 
 /// Builder for creating a DIDComm message for direct messages.
 #[derive(Debug, Clone, Default)]
@@ -13,24 +21,52 @@ pub struct DirectMessageBuilder {
     id: Option<String>,
     created_time: Option<u64>,
     expires_time: Option<u64>,
+    max_ttl: Option<u64>,
     to: Option<Vec<String>>,
     from: Option<String>,
+    thid: Option<String>,
+    pthid: Option<String>,
+    headers: BTreeMap<String, Value>,
+    created_time_now: bool,
+    clock: Option<Arc<dyn Clock + Send + Sync>>,
+    id_generator: Option<Arc<dyn IdGenerator + Send + Sync>>,
     message: Option<String>,
-    // TODO: the didcomm crate doesn't support lang?
-    // lang: Option<String>,
+    lang: Option<String>,
 }
 
 impl DirectMessageBuilder {
+    /// Creates a builder for a reply to `parent`.
+    /// The reply continues the thread of the parent (its `thid`, or its `id` if it starts the thread),
+    /// is addressed to the sender of the parent and sent from its first recipient.
+    pub fn reply_to(parent: &Message) -> Self {
+        let thid = parent.thid.clone().unwrap_or_else(|| parent.id.clone());
+        let mut builder = Self::new().thid(thid);
+        if let Some(from) = parent.from.clone() {
+            builder = builder.to(from);
+        }
+        if let Some(to) = parent.to.as_ref().and_then(|to| to.first()) {
+            builder = builder.from(to.clone());
+        }
+        builder
+    }
+
     /// Sets the content of the message.
     pub fn message(mut self, value: String) -> Self {
         self.message = Some(value);
         self
     }
 
+    /// Sets the language of the message content as a [BCP 47](https://www.rfc-editor.org/rfc/rfc5646) tag, e.g. "en".
+    /// The didcomm crate has no `lang` field, so it is sent as a custom top-level header.
+    pub fn lang(mut self, lang: String) -> Self {
+        self.lang = Some(lang);
+        self
+    }
+
     /// Builds the DIDComm message.
     pub fn build(self) -> Result<Message, MessageBuilderError> {
-        let type_ = "https://didcomm.org/basicmessage/2.0/message";
-        let id = self.id.clone().unwrap_or(Uuid::new_v4().to_string());
+        let type_ = DIRECT_MESSAGE_TYPE;
+        let id = self.id.clone().unwrap_or_else(|| self.next_id());
         let message = self
             .message
             .clone()
@@ -41,6 +77,13 @@ impl DirectMessageBuilder {
         });
         let mut didcomm_msg_builder = Message::build(id, type_.into(), body);
 
+        if let Some(lang) = self.lang.clone() {
+            if !is_valid_language_tag(&lang) {
+                return Err(MessageBuilderError::InvalidLanguageTag(lang));
+            }
+            didcomm_msg_builder = didcomm_msg_builder.header(LANG_HEADER.into(), lang.into());
+        }
+
         didcomm_msg_builder = self.add_common_to_builder(didcomm_msg_builder)?;
 
         Ok(didcomm_msg_builder.finalize())
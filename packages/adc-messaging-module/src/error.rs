@@ -1,23 +1,446 @@
+use alloc::string::{String, ToString};
 use core::fmt::Display;
 
 #[derive(Debug, Clone)]
 pub enum MessageBuilderError {
     MissingBody,
-    SerializationError,
+    SerializationError(String),
     MissingKey,
     MissingMessage,
     MissingMediaItem,
+    InvalidKey(String),
+    KidMismatch(String),
+    /// Key ID and key type.
+    UnsupportedKeyType(String, String),
+    /// Key ID and curve.
+    UnsupportedCurve(String, String),
+    /// Key ID and name of the field.
+    MissingKeyField(String, &'static str),
+    /// Key ID and name of the field.
+    UnexpectedKeyField(String, &'static str),
+    /// Key ID and name of the field.
+    InvalidKeyEncoding(String, &'static str),
+    /// Key ID and name of the field.
+    InvalidKeyLength(String, &'static str),
+    InvalidKeyPoint(String),
+    PrivateKeyMismatch(String),
+    /// Key ID and key usage.
+    InvalidKeyUse(String, String),
+    InvalidLanguageTag(String),
+    ReservedHeader(String),
+    MissingClock,
+    ExpiresBeforeCreated(u64),
+    ExpiresTooLate(u64),
+    ExpiresInPast(u64),
+    InvalidDid(String),
+    InvalidBase64(String),
+    /// Media item ID and media type.
+    InvalidMediaType(String, String),
+    /// Media item ID, declared media type and media type sniffed from the content.
+    MediaTypeMismatch(String, String, String),
+    /// Media item ID and the reason the ciphering is invalid.
+    InvalidCiphering(String, CipheringError),
 }
 
 impl Display for MessageBuilderError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let msg = match self {
-            MessageBuilderError::MissingBody => "Missing body in the message",
-            MessageBuilderError::SerializationError => "Error during message serialization",
-            MessageBuilderError::MissingKey => "Missing at least one key in the message",
-            MessageBuilderError::MissingMessage => "Missing message content",
-            MessageBuilderError::MissingMediaItem => "Missing media item",
-        };
-        write!(f, "{}", msg)
+        match self {
+            MessageBuilderError::MissingBody => write!(f, "Missing body in the message"),
+            MessageBuilderError::SerializationError(e) => {
+                write!(f, "Error during message serialization: {}", e)
+            }
+            MessageBuilderError::MissingKey => {
+                write!(f, "Missing at least one key in the message")
+            }
+            MessageBuilderError::MissingMessage => write!(f, "Missing message content"),
+            MessageBuilderError::MissingMediaItem => write!(f, "Missing media item"),
+            MessageBuilderError::InvalidKey(e) => write!(f, "Invalid key: {}", e),
+            MessageBuilderError::KidMismatch(kid) => {
+                write!(f, "Key ID does not match the key thumbprint: {}", kid)
+            }
+            MessageBuilderError::UnsupportedKeyType(kid, kty) => {
+                write!(f, "Key {}: unsupported key type {}", kid, kty)
+            }
+            MessageBuilderError::UnsupportedCurve(kid, crv) => {
+                write!(f, "Key {}: unsupported curve {}", kid, crv)
+            }
+            MessageBuilderError::MissingKeyField(kid, field) => {
+                write!(f, "Key {}: missing field {}", kid, field)
+            }
+            MessageBuilderError::UnexpectedKeyField(kid, field) => {
+                write!(f, "Key {}: unexpected field {}", kid, field)
+            }
+            MessageBuilderError::InvalidKeyEncoding(kid, field) => {
+                write!(f, "Key {}: field {} is not base64url encoded", kid, field)
+            }
+            MessageBuilderError::InvalidKeyLength(kid, field) => {
+                write!(f, "Key {}: field {} has an invalid length", kid, field)
+            }
+            MessageBuilderError::InvalidKeyPoint(kid) => {
+                write!(f, "Public key is not on the curve: {}", kid)
+            }
+            MessageBuilderError::PrivateKeyMismatch(kid) => {
+                write!(f, "Private key does not match the public key: {}", kid)
+            }
+            MessageBuilderError::InvalidKeyUse(kid, use_) => {
+                write!(f, "Key {}: invalid key usage {}", kid, use_)
+            }
+            MessageBuilderError::InvalidLanguageTag(lang) => {
+                write!(f, "Invalid BCP 47 language tag: {}", lang)
+            }
+            MessageBuilderError::ReservedHeader(name) => {
+                write!(
+                    f,
+                    "Reserved header can't be set as a custom header: {}",
+                    name
+                )
+            }
+            MessageBuilderError::MissingClock => write!(f, "Missing clock for the current time"),
+            MessageBuilderError::ExpiresBeforeCreated(expires_time) => {
+                write!(f, "Message expires before it is created: {}", expires_time)
+            }
+            MessageBuilderError::ExpiresTooLate(expires_time) => {
+                write!(f, "Message expires after its maximum TTL: {}", expires_time)
+            }
+            MessageBuilderError::ExpiresInPast(expires_time) => {
+                write!(
+                    f,
+                    "Message expires before the current time: {}",
+                    expires_time
+                )
+            }
+            MessageBuilderError::InvalidDid(did) => write!(f, "Invalid DID: {}", did),
+            MessageBuilderError::InvalidBase64(id) => {
+                write!(f, "Media item content is not base64 encoded: {}", id)
+            }
+            MessageBuilderError::InvalidMediaType(id, media_type) => {
+                write!(f, "Invalid media type of media item {}: {}", id, media_type)
+            }
+            MessageBuilderError::MediaTypeMismatch(id, declared, sniffed) => {
+                write!(
+                    f,
+                    "Media type {} does not match the content of media item {}: {}",
+                    declared, id, sniffed
+                )
+            }
+            MessageBuilderError::InvalidCiphering(id, e) => {
+                write!(f, "Invalid ciphering of media item {}: {}", id, e)
+            }
+        }
     }
 }
+
+impl MessageBuilderError {
+    /// Stable, machine-readable code of the error, e.g. for error handling in JavaScript.
+    pub fn code(&self) -> &'static str {
+        match self {
+            MessageBuilderError::MissingBody => "MISSING_BODY",
+            MessageBuilderError::SerializationError(_) => "SERIALIZATION_ERROR",
+            MessageBuilderError::MissingKey => "MISSING_KEY",
+            MessageBuilderError::MissingMessage => "MISSING_MESSAGE",
+            MessageBuilderError::MissingMediaItem => "MISSING_MEDIA_ITEM",
+            MessageBuilderError::InvalidKey(_) => "INVALID_KEY",
+            MessageBuilderError::KidMismatch(_) => "KID_MISMATCH",
+            MessageBuilderError::UnsupportedKeyType(_, _) => "UNSUPPORTED_KEY_TYPE",
+            MessageBuilderError::UnsupportedCurve(_, _) => "UNSUPPORTED_CURVE",
+            MessageBuilderError::MissingKeyField(_, _) => "MISSING_KEY_FIELD",
+            MessageBuilderError::UnexpectedKeyField(_, _) => "UNEXPECTED_KEY_FIELD",
+            MessageBuilderError::InvalidKeyEncoding(_, _) => "INVALID_KEY_ENCODING",
+            MessageBuilderError::InvalidKeyLength(_, _) => "INVALID_KEY_LENGTH",
+            MessageBuilderError::InvalidKeyPoint(_) => "INVALID_KEY_POINT",
+            MessageBuilderError::PrivateKeyMismatch(_) => "PRIVATE_KEY_MISMATCH",
+            MessageBuilderError::InvalidKeyUse(_, _) => "INVALID_KEY_USE",
+            MessageBuilderError::InvalidLanguageTag(_) => "INVALID_LANGUAGE_TAG",
+            MessageBuilderError::ReservedHeader(_) => "RESERVED_HEADER",
+            MessageBuilderError::MissingClock => "MISSING_CLOCK",
+            MessageBuilderError::ExpiresBeforeCreated(_) => "EXPIRES_BEFORE_CREATED",
+            MessageBuilderError::ExpiresTooLate(_) => "EXPIRES_TOO_LATE",
+            MessageBuilderError::ExpiresInPast(_) => "EXPIRES_IN_PAST",
+            MessageBuilderError::InvalidDid(_) => "INVALID_DID",
+            MessageBuilderError::InvalidBase64(_) => "INVALID_BASE64",
+            MessageBuilderError::InvalidMediaType(_, _) => "INVALID_MEDIA_TYPE",
+            MessageBuilderError::MediaTypeMismatch(_, _, _) => "MEDIA_TYPE_MISMATCH",
+            MessageBuilderError::InvalidCiphering(_, _) => "INVALID_CIPHERING",
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MessageBuilderError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            MessageBuilderError::InvalidCiphering(_, e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum MessageParserError {
+    UnknownMessageType(String),
+    MissingBodyField(&'static str),
+    InvalidBodyField(&'static str),
+    MissingAttachmentField(&'static str),
+    DanglingAttachmentId(String),
+    UnsupportedAttachmentData(String),
+    InvalidHeader(&'static str),
+    /// Media item ID and the reason the ciphering is invalid.
+    InvalidCiphering(String, CipheringError),
+}
+
+impl Display for MessageParserError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            MessageParserError::UnknownMessageType(type_) => {
+                write!(f, "Unknown message type: {}", type_)
+            }
+            MessageParserError::MissingBodyField(field) => {
+                write!(f, "Missing field in the message body: {}", field)
+            }
+            MessageParserError::InvalidBodyField(field) => {
+                write!(f, "Invalid field in the message body: {}", field)
+            }
+            MessageParserError::MissingAttachmentField(field) => {
+                write!(f, "Missing field in the attachment: {}", field)
+            }
+            MessageParserError::DanglingAttachmentId(id) => {
+                write!(f, "No attachment found for attachment_id: {}", id)
+            }
+            MessageParserError::UnsupportedAttachmentData(id) => {
+                write!(f, "Unsupported data in the attachment: {}", id)
+            }
+            MessageParserError::InvalidHeader(header) => {
+                write!(f, "Invalid header in the message: {}", header)
+            }
+            MessageParserError::InvalidCiphering(id, e) => {
+                write!(f, "Invalid ciphering of media item {}: {}", id, e)
+            }
+        }
+    }
+}
+
+impl MessageParserError {
+    /// Stable, machine-readable code of the error, see [`MessageBuilderError::code`].
+    pub fn code(&self) -> &'static str {
+        match self {
+            MessageParserError::UnknownMessageType(_) => "UNKNOWN_MESSAGE_TYPE",
+            MessageParserError::MissingBodyField(_) => "MISSING_BODY_FIELD",
+            MessageParserError::InvalidBodyField(_) => "INVALID_BODY_FIELD",
+            MessageParserError::MissingAttachmentField(_) => "MISSING_ATTACHMENT_FIELD",
+            MessageParserError::DanglingAttachmentId(_) => "DANGLING_ATTACHMENT_ID",
+            MessageParserError::UnsupportedAttachmentData(_) => "UNSUPPORTED_ATTACHMENT_DATA",
+            MessageParserError::InvalidHeader(_) => "INVALID_HEADER",
+            MessageParserError::InvalidCiphering(_, _) => "INVALID_BODY_CIPHERING",
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MessageParserError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            MessageParserError::InvalidCiphering(_, e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FreshnessError {
+    Expired(u64),
+    CreatedInFuture(u64),
+    ExpiresBeforeCreated(u64),
+}
+
+impl Display for FreshnessError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FreshnessError::Expired(expires_time) => {
+                write!(f, "Message has expired: {}", expires_time)
+            }
+            FreshnessError::CreatedInFuture(created_time) => {
+                write!(f, "Message is created in the future: {}", created_time)
+            }
+            FreshnessError::ExpiresBeforeCreated(expires_time) => {
+                write!(f, "Message expires before it is created: {}", expires_time)
+            }
+        }
+    }
+}
+
+impl FreshnessError {
+    /// Stable, machine-readable code of the error, see [`MessageBuilderError::code`].
+    pub fn code(&self) -> &'static str {
+        match self {
+            FreshnessError::Expired(_) => "MESSAGE_EXPIRED",
+            FreshnessError::CreatedInFuture(_) => "CREATED_IN_FUTURE",
+            FreshnessError::ExpiresBeforeCreated(_) => "MESSAGE_EXPIRES_BEFORE_CREATED",
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FreshnessError {}
+
+#[derive(Debug, Clone)]
+pub enum EnvelopeError {
+    DidNotResolved(String),
+    DidUrlNotFound(String),
+    SecretNotFound(String),
+    NoCompatibleCrypto(String),
+    Unsupported(String),
+    Malformed(String),
+    Other(String),
+}
+
+impl Display for EnvelopeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            EnvelopeError::DidNotResolved(e) => write!(f, "DID not resolved: {}", e),
+            EnvelopeError::DidUrlNotFound(e) => write!(f, "DID URL not found: {}", e),
+            EnvelopeError::SecretNotFound(e) => write!(f, "Secret not found: {}", e),
+            EnvelopeError::NoCompatibleCrypto(e) => write!(f, "No compatible crypto: {}", e),
+            EnvelopeError::Unsupported(e) => write!(f, "Unsupported crypto or method: {}", e),
+            EnvelopeError::Malformed(e) => write!(f, "Malformed envelope: {}", e),
+            EnvelopeError::Other(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl EnvelopeError {
+    /// Stable, machine-readable code of the error, see [`MessageBuilderError::code`].
+    pub fn code(&self) -> &'static str {
+        match self {
+            EnvelopeError::DidNotResolved(_) => "DID_NOT_RESOLVED",
+            EnvelopeError::DidUrlNotFound(_) => "DID_URL_NOT_FOUND",
+            EnvelopeError::SecretNotFound(_) => "SECRET_NOT_FOUND",
+            EnvelopeError::NoCompatibleCrypto(_) => "NO_COMPATIBLE_CRYPTO",
+            EnvelopeError::Unsupported(_) => "UNSUPPORTED_ENVELOPE",
+            EnvelopeError::Malformed(_) => "MALFORMED_ENVELOPE",
+            EnvelopeError::Other(_) => "ENVELOPE_ERROR",
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EnvelopeError {}
+
+impl From<didcomm::error::Error> for EnvelopeError {
+    fn from(e: didcomm::error::Error) -> Self {
+        let msg = e.to_string();
+        match e.kind() {
+            didcomm::error::ErrorKind::DIDNotResolved => EnvelopeError::DidNotResolved(msg),
+            didcomm::error::ErrorKind::DIDUrlNotFound => EnvelopeError::DidUrlNotFound(msg),
+            didcomm::error::ErrorKind::SecretNotFound => EnvelopeError::SecretNotFound(msg),
+            didcomm::error::ErrorKind::NoCompatibleCrypto => EnvelopeError::NoCompatibleCrypto(msg),
+            didcomm::error::ErrorKind::Unsupported => EnvelopeError::Unsupported(msg),
+            didcomm::error::ErrorKind::Malformed => EnvelopeError::Malformed(msg),
+            _ => EnvelopeError::Other(msg),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ResolverError {
+    InvalidDidDocument(String),
+    InvalidKey(String),
+    Other(String),
+}
+
+impl Display for ResolverError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ResolverError::InvalidDidDocument(e) => write!(f, "Invalid DID document: {}", e),
+            ResolverError::InvalidKey(e) => write!(f, "Invalid key: {}", e),
+            ResolverError::Other(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl ResolverError {
+    /// Stable, machine-readable code of the error, see [`MessageBuilderError::code`].
+    pub fn code(&self) -> &'static str {
+        match self {
+            ResolverError::InvalidDidDocument(_) => "INVALID_DID_DOCUMENT",
+            ResolverError::InvalidKey(_) => "INVALID_DID_DOCUMENT_KEY",
+            ResolverError::Other(_) => "RESOLVER_ERROR",
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ResolverError {}
+
+#[derive(Debug, Clone)]
+pub enum KeyError {
+    GenerationFailed(String),
+    UnsupportedKeyType(String),
+    MissingField(&'static str),
+}
+
+impl Display for KeyError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            KeyError::GenerationFailed(e) => write!(f, "Key generation failed: {}", e),
+            KeyError::UnsupportedKeyType(kty) => write!(f, "Unsupported key type: {}", kty),
+            KeyError::MissingField(field) => write!(f, "Missing field in the key: {}", field),
+        }
+    }
+}
+
+impl KeyError {
+    /// Stable, machine-readable code of the error, see [`MessageBuilderError::code`].
+    pub fn code(&self) -> &'static str {
+        match self {
+            KeyError::GenerationFailed(_) => "KEY_GENERATION_FAILED",
+            KeyError::UnsupportedKeyType(_) => "KEY_UNSUPPORTED_TYPE",
+            KeyError::MissingField(_) => "KEY_MISSING_FIELD",
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for KeyError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CipheringError {
+    EncryptionFailed,
+    DecryptionFailed,
+    InvalidParameter(&'static str),
+    UnsupportedAlgorithm(String),
+}
+
+impl Display for CipheringError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            CipheringError::EncryptionFailed => write!(f, "Failed to encrypt content"),
+            CipheringError::DecryptionFailed => {
+                write!(
+                    f,
+                    "Failed to decrypt content: wrong key or modified ciphertext"
+                )
+            }
+            CipheringError::InvalidParameter(parameter) => {
+                write!(f, "Invalid ciphering parameter: {}", parameter)
+            }
+            CipheringError::UnsupportedAlgorithm(algorithm) => {
+                write!(f, "Unsupported ciphering algorithm: {}", algorithm)
+            }
+        }
+    }
+}
+
+impl CipheringError {
+    /// Stable, machine-readable code of the error, see [`MessageBuilderError::code`].
+    pub fn code(&self) -> &'static str {
+        match self {
+            CipheringError::EncryptionFailed => "ENCRYPTION_FAILED",
+            CipheringError::DecryptionFailed => "DECRYPTION_FAILED",
+            CipheringError::InvalidParameter(_) => "INVALID_CIPHERING_PARAMETER",
+            CipheringError::UnsupportedAlgorithm(_) => "UNSUPPORTED_CIPHERING_ALGORITHM",
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CipheringError {}
@@ -0,0 +1,32 @@
+use didcomm::Message;
+
+use crate::error::FreshnessError;
+
+/// Maximum time between `created_time` (or the current time) and `expires_time` accepted by the builders,
+/// unless set otherwise with `max_ttl`: 365 days, in seconds.
+pub const DEFAULT_MAX_TTL: u64 = 365 * 24 * 60 * 60;
+
+/// Checks that a received message is valid at `now`, allowing `skew` seconds of clock difference
+/// between sender and receiver. Times are in seconds since the Unix epoch.
+/// Messages without `created_time` or `expires_time` are not checked against them.
+pub fn check_freshness(message: &Message, now: u64, skew: u64) -> Result<(), FreshnessError> {
+    if let (Some(created_time), Some(expires_time)) = (message.created_time, message.expires_time) {
+        if expires_time <= created_time {
+            return Err(FreshnessError::ExpiresBeforeCreated(expires_time));
+        }
+    }
+
+    if let Some(created_time) = message.created_time {
+        if created_time > now.saturating_add(skew) {
+            return Err(FreshnessError::CreatedInFuture(created_time));
+        }
+    }
+
+    if let Some(expires_time) = message.expires_time {
+        if expires_time.saturating_add(skew) <= now {
+            return Err(FreshnessError::Expired(expires_time));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use alloc::{
+    string::{String, ToString},
+    sync::Arc,
+};
+use core::{
+    fmt::Debug,
+    sync::atomic::{AtomicU64, Ordering},
+};
+use uuid::Uuid;
+
+use crate::clock::Clock;
+
+/// Source of message IDs and media item `@id`s.
+pub trait IdGenerator: Debug {
+    /// Returns a new ID.
+    fn next_id(&self) -> String;
+}
+
+/// Generates random UUIDv4 IDs. Used by builders that have no ID generator set.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RandomIdGenerator;
+
+impl IdGenerator for RandomIdGenerator {
+    fn next_id(&self) -> String {
+        Uuid::new_v4().to_string()
+    }
+}
+
+/// Generates time-ordered [UUIDv7](https://www.rfc-editor.org/rfc/rfc9562#section-5.7) IDs:
+/// a 48 bit millisecond timestamp from the clock followed by random bits,
+/// so IDs sort by creation time.
+#[derive(Debug, Clone)]
+pub struct TimeOrderedIdGenerator {
+    clock: Arc<dyn Clock + Send + Sync>,
+}
+
+impl TimeOrderedIdGenerator {
+    /// Creates a generator reading the time from `clock`.
+    pub fn new(clock: impl Clock + Send + Sync + 'static) -> Self {
+        Self {
+            clock: Arc::new(clock),
+        }
+    }
+}
+
+impl IdGenerator for TimeOrderedIdGenerator {
+    fn next_id(&self) -> String {
+        let mut bytes = *Uuid::new_v4().as_bytes();
+        bytes[..6].copy_from_slice(&self.clock.now_millis().to_be_bytes()[2..]);
+        uuid_string(bytes, 7)
+    }
+}
+
+/// Generates a reproducible sequence of UUIDv4-formatted IDs from a seed, for tests and fixtures.
+#[derive(Debug)]
+pub struct SeededIdGenerator {
+    state: AtomicU64,
+}
+
+impl SeededIdGenerator {
+    /// Creates a generator whose sequence of IDs is determined by `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            state: AtomicU64::new(seed),
+        }
+    }
+
+    // SplitMix64, see https://prng.di.unimi.it/splitmix64.c
+    fn next_u64(&self) -> u64 {
+        let mut z = self
+            .state
+            .fetch_add(0x9e37_79b9_7f4a_7c15, Ordering::Relaxed)
+            .wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+impl IdGenerator for SeededIdGenerator {
+    fn next_id(&self) -> String {
+        let mut bytes = [0u8; 16];
+        bytes[..8].copy_from_slice(&self.next_u64().to_be_bytes());
+        bytes[8..].copy_from_slice(&self.next_u64().to_be_bytes());
+        uuid_string(bytes, 4)
+    }
+}
+
+/// Formats `bytes` as a UUID of the given version with the RFC 4122 variant.
+fn uuid_string(mut bytes: [u8; 16], version: u8) -> String {
+    bytes[6] = (bytes[6] & 0x0f) | (version << 4);
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    Uuid::from_bytes(bytes).to_string()
+}
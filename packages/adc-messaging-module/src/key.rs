@@ -1,21 +1,206 @@
-use alloc::string::String;
+use alloc::{collections::BTreeMap, string::String};
+use core::{
+    fmt::Debug,
+    ops::{Deref, DerefMut},
+};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+use zeroize::{Zeroize, Zeroizing};
 
+use crate::error::KeyError;
+
+/// Public [JSON Web Key](https://datatracker.ietf.org/doc/html/rfc7517).
+/// EC keys (`kty` "EC") have an `x` and a `y` coordinate, OKP keys (`kty` "OKP", e.g. Ed25519 and X25519) only `x`.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-pub struct JsonWebKey {
+pub struct PublicJwk {
     /// Key type
     pub kty: String,
     /// Curve
     pub crv: String,
     /// x-coordinate
     pub x: String,
-    /// y-coordinate
-    pub y: String,
-    /// Private key
-    pub d: String,
+    /// y-coordinate, absent for OKP keys
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub y: Option<String>,
     /// Key usage
     #[serde(rename = "use")]
     pub use_: String,
     /// Key ID
     pub kid: String,
 }
+
+impl PublicJwk {
+    /// Computes the [JWK thumbprint](https://datatracker.ietf.org/doc/html/rfc7638) of the key:
+    /// the base64url encoded SHA-256 hash of its required members (`crv`, `kty`, `x` and, for EC keys, `y`).
+    pub fn thumbprint(&self) -> Result<String, KeyError> {
+        let mut members = BTreeMap::new();
+        members.insert("crv", self.crv.as_str());
+        members.insert("kty", self.kty.as_str());
+        members.insert("x", self.x.as_str());
+        match self.kty.as_str() {
+            "EC" => {
+                let y = self.y.as_deref().ok_or(KeyError::MissingField("y"))?;
+                members.insert("y", y);
+            }
+            "OKP" => {}
+            kty => return Err(KeyError::UnsupportedKeyType(kty.into())),
+        }
+
+        // Members are serialized in lexicographic order without whitespace.
+        let json = serde_json::to_vec(&members).expect("string map serializes to JSON");
+        Ok(base64::encode_config(
+            Sha256::digest(&json),
+            base64::URL_SAFE_NO_PAD,
+        ))
+    }
+}
+
+/// Private JSON Web Key: a [`PublicJwk`] together with the private key `d`.
+/// Derefs to its public part; use [`PrivateJwk::public_key`] or `PublicJwk::from` to obtain a key that can be shared
+/// without the private key.
+/// The private key is zeroized on drop and redacted in `Debug` output.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PrivateJwk {
+    #[serde(flatten)]
+    public: PublicJwk,
+    d: SecretString,
+}
+
+impl PrivateJwk {
+    /// Creates a private key from its public part and the private key `d`.
+    pub fn new(public: PublicJwk, d: String) -> Self {
+        Self {
+            public,
+            d: SecretString(d),
+        }
+    }
+
+    /// Public part of the key.
+    pub fn public_key(&self) -> &PublicJwk {
+        &self.public
+    }
+
+    /// Private key
+    pub fn d(&self) -> &str {
+        &self.d.0
+    }
+
+    /// Splits the key into its public part and the private key `d`, without copying `d`.
+    pub fn into_parts(self) -> (PublicJwk, Zeroizing<String>) {
+        let PrivateJwk { public, mut d } = self;
+        (public, Zeroizing::new(core::mem::take(&mut d.0)))
+    }
+}
+
+impl Deref for PrivateJwk {
+    type Target = PublicJwk;
+
+    fn deref(&self) -> &PublicJwk {
+        &self.public
+    }
+}
+
+impl DerefMut for PrivateJwk {
+    fn deref_mut(&mut self) -> &mut PublicJwk {
+        &mut self.public
+    }
+}
+
+impl From<PrivateJwk> for PublicJwk {
+    fn from(key: PrivateJwk) -> Self {
+        key.public
+    }
+}
+
+/// Secret string that is zeroized on drop, never formatted and compared in constant time.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+struct SecretString(String);
+
+impl Drop for SecretString {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl PartialEq for SecretString {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_bytes().ct_eq(other.0.as_bytes()).into()
+    }
+}
+
+impl Eq for SecretString {}
+
+impl Debug for SecretString {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "[REDACTED]")
+    }
+}
+
+/// JSON Web Key that is either private or public, e.g. a key of a key set.
+/// Derefs to the public part of the key.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum JsonWebKey {
+    /// Key with private key `d`
+    Private(PrivateJwk),
+    /// Key without private key
+    Public(PublicJwk),
+}
+
+impl JsonWebKey {
+    /// Public part of the key.
+    pub fn public_key(&self) -> &PublicJwk {
+        match self {
+            JsonWebKey::Private(key) => key.public_key(),
+            JsonWebKey::Public(key) => key,
+        }
+    }
+
+    /// Private key, if the key has one.
+    pub fn private_key(&self) -> Option<&PrivateJwk> {
+        match self {
+            JsonWebKey::Private(key) => Some(key),
+            JsonWebKey::Public(_) => None,
+        }
+    }
+}
+
+impl Deref for JsonWebKey {
+    type Target = PublicJwk;
+
+    fn deref(&self) -> &PublicJwk {
+        self.public_key()
+    }
+}
+
+impl DerefMut for JsonWebKey {
+    fn deref_mut(&mut self) -> &mut PublicJwk {
+        match self {
+            JsonWebKey::Private(key) => key,
+            JsonWebKey::Public(key) => key,
+        }
+    }
+}
+
+impl From<PrivateJwk> for JsonWebKey {
+    fn from(key: PrivateJwk) -> Self {
+        JsonWebKey::Private(key)
+    }
+}
+
+impl From<PublicJwk> for JsonWebKey {
+    fn from(key: PublicJwk) -> Self {
+        JsonWebKey::Public(key)
+    }
+}
+
+impl From<JsonWebKey> for PublicJwk {
+    fn from(key: JsonWebKey) -> Self {
+        match key {
+            JsonWebKey::Private(key) => key.into(),
+            JsonWebKey::Public(key) => key,
+        }
+    }
+}
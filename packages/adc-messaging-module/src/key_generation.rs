@@ -0,0 +1,83 @@
+use alloc::string::{String, ToString};
+use askar_crypto::{
+    alg::{ed25519::Ed25519KeyPair, k256::K256KeyPair, p256::P256KeyPair, x25519::X25519KeyPair},
+    jwk::ToJwk,
+    repr::KeyGen,
+};
+use rand_core::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+
+use crate::{error::KeyError, PrivateJwk, PublicJwk};
+
+/// Curve of a generated key.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum KeyCurve {
+    /// NIST P-256 key (`kty` "EC") for key agreement.
+    #[serde(rename = "P-256")]
+    P256,
+    /// X25519 key (`kty` "OKP") for key agreement.
+    X25519,
+    /// Ed25519 key (`kty` "OKP") for signing.
+    Ed25519,
+    /// secp256k1 key (`kty` "EC") for signing.
+    #[serde(rename = "secp256k1")]
+    Secp256k1,
+}
+
+impl KeyCurve {
+    /// Key usage (`use`) of keys generated for the curve.
+    pub fn key_use(&self) -> &'static str {
+        match self {
+            KeyCurve::P256 | KeyCurve::X25519 => "enc",
+            KeyCurve::Ed25519 | KeyCurve::Secp256k1 => "sig",
+        }
+    }
+}
+
+/// Generates a key on the given curve from the caller's random number generator.
+/// The `kid` of the key is its [JWK thumbprint](https://datatracker.ietf.org/doc/html/rfc7638).
+pub fn generate_key(
+    curve: KeyCurve,
+    rng: &mut (impl CryptoRng + RngCore),
+) -> Result<PrivateJwk, KeyError> {
+    match curve {
+        KeyCurve::P256 => to_private_jwk::<P256KeyPair>(curve, rng),
+        KeyCurve::X25519 => to_private_jwk::<X25519KeyPair>(curve, rng),
+        KeyCurve::Ed25519 => to_private_jwk::<Ed25519KeyPair>(curve, rng),
+        KeyCurve::Secp256k1 => to_private_jwk::<K256KeyPair>(curve, rng),
+    }
+}
+
+fn to_private_jwk<K: KeyGen + ToJwk>(
+    curve: KeyCurve,
+    rng: &mut (impl CryptoRng + RngCore),
+) -> Result<PrivateJwk, KeyError> {
+    #[derive(Deserialize)]
+    struct Jwk {
+        kty: String,
+        crv: String,
+        x: String,
+        y: Option<String>,
+        d: String,
+    }
+
+    let key = K::generate(rng).map_err(|e| KeyError::GenerationFailed(e.to_string()))?;
+    let kid = key
+        .to_jwk_thumbprint(None)
+        .map_err(|e| KeyError::GenerationFailed(e.to_string()))?;
+    let jwk = key
+        .to_jwk_secret(None)
+        .map_err(|e| KeyError::GenerationFailed(e.to_string()))?;
+    let jwk: Jwk =
+        serde_json::from_slice(&jwk).map_err(|e| KeyError::GenerationFailed(e.to_string()))?;
+
+    let public = PublicJwk {
+        kty: jwk.kty,
+        crv: jwk.crv,
+        x: jwk.x,
+        y: jwk.y,
+        use_: curve.key_use().into(),
+        kid,
+    };
+    Ok(PrivateJwk::new(public, jwk.d))
+}
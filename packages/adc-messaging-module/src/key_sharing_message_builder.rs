@@ -1,10 +1,16 @@
 use alloc::string::ToString;
-use alloc::{string::String, vec::Vec};
+use alloc::{collections::BTreeMap, string::String, sync::Arc, vec::Vec};
 use didcomm::Message;
 use didcomm::MessageBuilder;
-use uuid::Uuid;
+use serde_json::Value;
 
-use crate::{error::MessageBuilderError, impl_common_builder, JsonWebKey};
+use crate::{
+    clock::Clock, error::MessageBuilderError, id_generator::IdGenerator, impl_common_builder,
+    key_validation::validate_key, JsonWebKey,
+};
+
+/// Message type URI of a key sharing message.
+pub const KEY_SHARING_MESSAGE_TYPE: &str = "https://didcomm.org/key-sharing/1.0/send-keys";
 
 /// Builder for creating a DIDComm message for key sharing.
 /// Keys are structured as a [JWK set format](https://datatracker.ietf.org/doc/html/rfc7517#section-5).
@@ -13,15 +19,31 @@ pub struct KeySharingMessageBuilder {
     id: Option<String>,
     created_time: Option<u64>,
     expires_time: Option<u64>,
+    max_ttl: Option<u64>,
     to: Option<Vec<String>>,
     from: Option<String>,
+    thid: Option<String>,
+    pthid: Option<String>,
+    headers: BTreeMap<String, Value>,
+    created_time_now: bool,
+    clock: Option<Arc<dyn Clock + Send + Sync>>,
+    id_generator: Option<Arc<dyn IdGenerator + Send + Sync>>,
     keys: Vec<JsonWebKey>,
+    thumbprint_kids: bool,
 }
 
 impl KeySharingMessageBuilder {
-    /// Adds a key to the key set.
-    pub fn add_key(mut self, value: JsonWebKey) -> Self {
-        self.keys.push(value);
+    /// Adds a public or private key to the key set.
+    pub fn add_key(mut self, value: impl Into<JsonWebKey>) -> Self {
+        self.keys.push(value.into());
+        self
+    }
+
+    /// Derives key IDs from the [JWK thumbprints](https://datatracker.ietf.org/doc/html/rfc7638) of the keys.
+    /// Keys with an empty `kid` get their thumbprint; keys whose `kid` differs from their thumbprint are rejected
+    /// when the message is built.
+    pub fn thumbprint_kids(mut self, thumbprint_kids: bool) -> Self {
+        self.thumbprint_kids = thumbprint_kids;
         self
     }
 
@@ -29,13 +51,25 @@ impl KeySharingMessageBuilder {
     pub fn build(self) -> Result<Message, MessageBuilderError> {
         // TODO: A write-up of the protocol describing the message type must be submitted to didcomm.org.
         // See https://github.com/decentralized-identity/didcomm.org/blob/main/docs/pr-guide.md to learn how.
-        let type_ = "https://didcomm.org/key-sharing/1.0/send-keys";
-        let id = self.id.clone().unwrap_or(Uuid::new_v4().to_string());
+        let type_ = KEY_SHARING_MESSAGE_TYPE;
+        let id = self.id.clone().unwrap_or_else(|| self.next_id());
         if self.keys.is_empty() {
             return Err(MessageBuilderError::MissingKey);
         }
+        for key in &self.keys {
+            validate_key(key)?;
+        }
+        let keys = if self.thumbprint_kids {
+            self.keys
+                .iter()
+                .cloned()
+                .map(with_thumbprint_kid)
+                .collect::<Result<Vec<_>, _>>()?
+        } else {
+            self.keys.clone()
+        };
         let body = serde_json::json!({
-            "keys" : self.keys
+            "keys" : keys
         });
 
         let mut didcomm_msg_builder = Message::build(id, type_.into(), body);
@@ -47,3 +81,15 @@ impl KeySharingMessageBuilder {
 }
 
 impl_common_builder!(KeySharingMessageBuilder);
+
+fn with_thumbprint_kid(mut key: JsonWebKey) -> Result<JsonWebKey, MessageBuilderError> {
+    let thumbprint = key
+        .thumbprint()
+        .map_err(|e| MessageBuilderError::InvalidKey(e.to_string()))?;
+    if key.kid.is_empty() {
+        key.kid = thumbprint;
+    } else if key.kid != thumbprint {
+        return Err(MessageBuilderError::KidMismatch(key.kid.clone()));
+    }
+    Ok(key)
+}
@@ -0,0 +1,95 @@
+use askar_crypto::{
+    alg::{ed25519::Ed25519KeyPair, k256::K256KeyPair, p256::P256KeyPair, x25519::X25519KeyPair},
+    jwk::FromJwk,
+};
+use serde::Serialize;
+use zeroize::Zeroizing;
+
+use crate::{error::MessageBuilderError, JsonWebKey};
+
+/// Checks that a key is a well-formed EC or OKP key before it is shared:
+/// supported `kty`/`crv`, base64url encoded members of the curve's length, a public key on the curve,
+/// a private key matching the public key and a `use` of "enc" or "sig".
+pub(crate) fn validate_key(key: &JsonWebKey) -> Result<(), MessageBuilderError> {
+    if key.use_ != "enc" && key.use_ != "sig" {
+        return Err(MessageBuilderError::InvalidKeyUse(
+            key.kid.clone(),
+            key.use_.clone(),
+        ));
+    }
+
+    let is_ec = match (key.kty.as_str(), key.crv.as_str()) {
+        ("EC", "P-256" | "secp256k1") => true,
+        ("OKP", "X25519" | "Ed25519") => false,
+        ("EC" | "OKP", crv) => {
+            return Err(MessageBuilderError::UnsupportedCurve(
+                key.kid.clone(),
+                crv.into(),
+            ))
+        }
+        (kty, _) => {
+            return Err(MessageBuilderError::UnsupportedKeyType(
+                key.kid.clone(),
+                kty.into(),
+            ))
+        }
+    };
+    // All supported curves have 32 byte coordinates and private keys.
+    let length = 32;
+
+    decode_member(&key.kid, "x", &key.x, length)?;
+    match (is_ec, &key.y) {
+        (true, Some(y)) => decode_member(&key.kid, "y", y, length)?,
+        (true, None) => return Err(MessageBuilderError::MissingKeyField(key.kid.clone(), "y")),
+        (false, Some(_)) => {
+            return Err(MessageBuilderError::UnexpectedKeyField(
+                key.kid.clone(),
+                "y",
+            ))
+        }
+        (false, None) => {}
+    }
+    if let Some(private_key) = key.private_key() {
+        decode_member(&key.kid, "d", private_key.d(), length)?;
+    }
+
+    if !is_valid_jwk(&key.crv, key.public_key()) {
+        return Err(MessageBuilderError::InvalidKeyPoint(key.kid.clone()));
+    }
+    if let Some(private_key) = key.private_key() {
+        if !is_valid_jwk(&key.crv, private_key) {
+            return Err(MessageBuilderError::PrivateKeyMismatch(key.kid.clone()));
+        }
+    }
+    Ok(())
+}
+
+fn decode_member(
+    kid: &str,
+    name: &'static str,
+    value: &str,
+    length: usize,
+) -> Result<(), MessageBuilderError> {
+    let bytes = base64::decode_config(value, base64::URL_SAFE_NO_PAD)
+        .map_err(|_| MessageBuilderError::InvalidKeyEncoding(kid.into(), name))?;
+    if bytes.len() != length {
+        return Err(MessageBuilderError::InvalidKeyLength(kid.into(), name));
+    }
+    Ok(())
+}
+
+/// Imports the key to check that its point is on the curve and, for private keys,
+/// that the private key derives the public key.
+fn is_valid_jwk(crv: &str, key: &impl Serialize) -> bool {
+    // The JSON of a private key contains `d`.
+    let Ok(jwk) = serde_json::to_string(key).map(Zeroizing::new) else {
+        return false;
+    };
+    match crv {
+        "P-256" => P256KeyPair::from_jwk(&jwk).is_ok(),
+        "secp256k1" => K256KeyPair::from_jwk(&jwk).is_ok(),
+        "X25519" => X25519KeyPair::from_jwk(&jwk).is_ok(),
+        "Ed25519" => Ed25519KeyPair::from_jwk(&jwk).is_ok(),
+        _ => false,
+    }
+}
@@ -0,0 +1,86 @@
+use core::iter::Peekable;
+
+/// Checks that `tag` is a well-formed [BCP 47](https://www.rfc-editor.org/rfc/rfc5646#section-2.1) language tag,
+/// e.g. "en", "de-CH" or "zh-Hant-TW". Subtags are not checked against the IANA registry,
+/// and grandfathered tags are not accepted.
+pub(crate) fn is_valid_language_tag(tag: &str) -> bool {
+    let mut subtags = tag.split('-').peekable();
+
+    // Private use tags, e.g. "x-whatever".
+    if subtags.peek().is_some_and(|s| s.eq_ignore_ascii_case("x")) {
+        subtags.next();
+        return is_private_use(subtags);
+    }
+
+    // language = 2*3ALPHA ["-" extlang] / 4ALPHA / 5*8ALPHA
+    let Some(language) = subtags.next() else {
+        return false;
+    };
+    if !is_alpha(language, 2, 8) {
+        return false;
+    }
+    if language.len() <= 3 {
+        // extlang = 3ALPHA *2("-" 3ALPHA)
+        for _ in 0..3 {
+            if !next_if(&mut subtags, |s| is_alpha(s, 3, 3)) {
+                break;
+            }
+        }
+    }
+    // script = 4ALPHA
+    next_if(&mut subtags, |s| is_alpha(s, 4, 4));
+    // region = 2ALPHA / 3DIGIT
+    next_if(&mut subtags, |s| {
+        is_alpha(s, 2, 2) || (s.len() == 3 && s.bytes().all(|b| b.is_ascii_digit()))
+    });
+    // variant = 5*8alphanum / (DIGIT 3alphanum)
+    while next_if(&mut subtags, |s| {
+        is_alphanumeric(s, 5, 8) || (is_alphanumeric(s, 4, 4) && s.as_bytes()[0].is_ascii_digit())
+    }) {}
+    // extension = singleton 1*("-" (2*8alphanum))
+    while let Some(singleton) = subtags.peek() {
+        if singleton.len() != 1 || singleton.eq_ignore_ascii_case("x") {
+            break;
+        }
+        if !is_alphanumeric(singleton, 1, 1) {
+            return false;
+        }
+        subtags.next();
+        if !next_if(&mut subtags, |s| is_alphanumeric(s, 2, 8)) {
+            return false;
+        }
+        while next_if(&mut subtags, |s| is_alphanumeric(s, 2, 8)) {}
+    }
+    // privateuse = "x" 1*("-" (1*8alphanum))
+    match subtags.next() {
+        None => true,
+        Some(x) if x.eq_ignore_ascii_case("x") => is_private_use(subtags),
+        Some(_) => false,
+    }
+}
+
+fn is_private_use<'a>(subtags: impl Iterator<Item = &'a str>) -> bool {
+    let mut count = 0;
+    for subtag in subtags {
+        if !is_alphanumeric(subtag, 1, 8) {
+            return false;
+        }
+        count += 1;
+    }
+    count > 0
+}
+
+fn next_if<'a, I: Iterator<Item = &'a str>>(
+    subtags: &mut Peekable<I>,
+    predicate: impl Fn(&str) -> bool,
+) -> bool {
+    subtags.next_if(|s| predicate(s)).is_some()
+}
+
+fn is_alpha(subtag: &str, min: usize, max: usize) -> bool {
+    (min..=max).contains(&subtag.len()) && subtag.bytes().all(|b| b.is_ascii_alphabetic())
+}
+
+fn is_alphanumeric(subtag: &str, min: usize, max: usize) -> bool {
+    (min..=max).contains(&subtag.len()) && subtag.bytes().all(|b| b.is_ascii_alphanumeric())
+}
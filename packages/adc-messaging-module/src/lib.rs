@@ -2,22 +2,62 @@
 
 #[macro_use]
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
+mod ciphering;
+mod clock;
+mod did_resolver;
+mod did_validation;
 mod direct_message_builder;
 mod error;
+mod freshness;
+mod id_generator;
 mod key;
+mod key_generation;
 mod key_sharing_message_builder;
+mod key_validation;
+mod language_tag;
 mod media_item_inlined;
 mod media_item_message_builder;
 mod media_item_referenced;
+mod media_type;
+mod message_parser;
+mod multihash;
+mod pack;
+mod recipients;
+mod secrets_resolver;
+mod unpack;
 #[macro_use]
 mod common_builder_macro;
 
+pub use ciphering::{
+    decrypt_content, encrypt_content, CipherAlgorithm, Ciphering, CipheringParameters,
+    EncryptedContent,
+};
+#[cfg(feature = "std")]
+pub use clock::SystemClock;
+pub use clock::{Clock, FixedClock};
+pub use did_resolver::{DidResolver, InMemoryDidResolver};
 pub use didcomm;
-pub use direct_message_builder::DirectMessageBuilder;
-pub use error::MessageBuilderError;
+pub use direct_message_builder::{DirectMessageBuilder, DIRECT_MESSAGE_TYPE};
+pub use error::{
+    CipheringError, EnvelopeError, FreshnessError, KeyError, MessageBuilderError,
+    MessageParserError, ResolverError,
+};
+pub use freshness::{check_freshness, DEFAULT_MAX_TTL};
+pub use id_generator::{IdGenerator, RandomIdGenerator, SeededIdGenerator, TimeOrderedIdGenerator};
 pub use key::*;
-pub use key_sharing_message_builder::KeySharingMessageBuilder;
+pub use key_generation::{generate_key, KeyCurve};
+pub use key_sharing_message_builder::{KeySharingMessageBuilder, KEY_SHARING_MESSAGE_TYPE};
 pub use media_item_inlined::MediaItemInlined;
-pub use media_item_message_builder::MediaItemsMessageBuilder;
+pub use media_item_message_builder::{MediaItemsMessageBuilder, MEDIA_ITEMS_MESSAGE_TYPE};
 pub use media_item_referenced::MediaItemReferenced;
+pub use message_parser::{
+    parse_message, DirectMessage, KeySharingMessage, MediaItem, MediaItemsMessage, ParsedMessage,
+};
+pub use multihash::{multihash, verify_multihash, HashAlgorithm, MultihashHasher};
+pub use pack::{pack_anoncrypt, pack_authcrypt, PackedMessage};
+pub use recipients::split_by_recipient;
+pub use secrets_resolver::{InMemorySecretsResolver, SecretsResolver};
+pub use unpack::{unpack, UnpackedMessage};
@@ -2,19 +2,19 @@ use alloc::string::{String, ToString};
 use didcomm::{Attachment, Base64AttachmentData};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use uuid::Uuid;
 
 use crate::{
     media_item_referenced::{MediaItemInBody, MediaItemTrait},
-    MessageBuilderError,
+    media_type::{is_valid_media_type, matches_sniffed_media_type, sniff_media_type, OCTET_STREAM},
+    MessageBuilderError, MessageParserError,
 };
 
 // / Represents a media item with inline content in a DIDComm message.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct MediaItemInlined {
     /// Attachment ID.
     pub id: String,
-    /// Media type of file.
+    /// Media type of file, e.g. "image/png". Filled in from the content when left empty.
     pub media_type: String,
     /// File name.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -24,16 +24,25 @@ pub struct MediaItemInlined {
     pub description: Option<String>,
     /// File contents encoded as base64.
     pub base64: String,
+    /// Format of the file if the media type is not sufficient.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// Last modification time of the file, in seconds since the Unix epoch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lastmod_time: Option<u64>,
+    /// Size of the file in bytes. Computed from `base64` when the message is built.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub byte_count: Option<u64>,
 }
 
 impl MediaItemTrait for MediaItemInlined {
-    fn to_body_item(&self) -> Result<Value, MessageBuilderError> {
+    fn to_body_item(&self, id: String) -> Result<Value, MessageBuilderError> {
         serde_json::to_value(MediaItemInBody {
-            id: Uuid::new_v4().to_string(),
+            id,
             attachment_id: self.id.clone(),
             ciphering: None,
         })
-        .map_err(|_| MessageBuilderError::SerializationError)
+        .map_err(|e| MessageBuilderError::SerializationError(e.to_string()))
     }
 
     fn into_attachment(self) -> Attachment {
@@ -48,9 +57,68 @@ impl MediaItemTrait for MediaItemInlined {
             },
             description: self.description,
             filename: self.filename,
-            format: None,
-            lastmod_time: None,
-            byte_count: None,
+            format: self.format,
+            lastmod_time: self.lastmod_time,
+            byte_count: self.byte_count,
+        }
+    }
+}
+
+impl MediaItemInlined {
+    /// Sets `byte_count` to the size of the decoded `base64` content and checks `media_type` against it.
+    /// Standard and URL-safe base64 are accepted, with or without padding.
+    /// An empty media type is filled in from the magic bytes of the content,
+    /// falling back to `application/octet-stream`.
+    pub(crate) fn with_checked_content(mut self) -> Result<Self, MessageBuilderError> {
+        let base64 = self.base64.trim_end_matches('=');
+        let content = base64::decode_config(base64, base64::STANDARD_NO_PAD)
+            .or_else(|_| base64::decode_config(base64, base64::URL_SAFE_NO_PAD))
+            .map_err(|_| MessageBuilderError::InvalidBase64(self.id.clone()))?;
+        self.byte_count = Some(content.len() as u64);
+
+        let sniffed = sniff_media_type(&content);
+        if self.media_type.is_empty() {
+            self.media_type = sniffed.unwrap_or(OCTET_STREAM).to_string();
+            return Ok(self);
+        }
+        if !is_valid_media_type(&self.media_type) {
+            return Err(MessageBuilderError::InvalidMediaType(
+                self.id,
+                self.media_type,
+            ));
         }
+        if matches_sniffed_media_type(&self.media_type, sniffed) {
+            Ok(self)
+        } else {
+            Err(MessageBuilderError::MediaTypeMismatch(
+                self.id,
+                self.media_type,
+                sniffed.unwrap_or(OCTET_STREAM).to_string(),
+            ))
+        }
+    }
+
+    /// Rebuilds an inlined media item from the attachment it was serialized into.
+    pub(crate) fn from_attachment(attachment: Attachment) -> Result<Self, MessageParserError> {
+        let id = attachment
+            .id
+            .ok_or(MessageParserError::MissingAttachmentField("id"))?;
+        let base64 = match attachment.data {
+            didcomm::AttachmentData::Base64 { value } => value.base64,
+            _ => return Err(MessageParserError::UnsupportedAttachmentData(id)),
+        };
+
+        Ok(MediaItemInlined {
+            id,
+            media_type: attachment
+                .media_type
+                .ok_or(MessageParserError::MissingAttachmentField("media_type"))?,
+            filename: attachment.filename,
+            description: attachment.description,
+            base64,
+            format: attachment.format,
+            lastmod_time: attachment.lastmod_time,
+            byte_count: attachment.byte_count,
+        })
     }
 }
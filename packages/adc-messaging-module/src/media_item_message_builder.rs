@@ -1,22 +1,29 @@
-use alloc::{
-    string::{String, ToString},
-    vec::Vec,
-};
+use alloc::{collections::BTreeMap, string::String, sync::Arc, vec::Vec};
 use didcomm::{Message, MessageBuilder};
-use uuid::Uuid;
+use serde_json::Value;
 
 use crate::{
-    error::MessageBuilderError, impl_common_builder, media_item_referenced::MediaItemTrait,
-    MediaItemInlined, MediaItemReferenced,
+    clock::Clock, error::MessageBuilderError, id_generator::IdGenerator, impl_common_builder,
+    media_item_referenced::MediaItemTrait, MediaItemInlined, MediaItemReferenced,
 };
 
+/// Message type URI of a [Media Sharing](https://didcomm.org/media-sharing/1.0/) message.
+pub const MEDIA_ITEMS_MESSAGE_TYPE: &str = "https://didcomm.org/media-sharing/1.0/share-media";
+
 /// Builder for creating a DIDComm message for media items.
 #[derive(Default)]
 pub struct MediaItemsMessageBuilder {
     to: Option<Vec<String>>,
     from: Option<String>,
+    thid: Option<String>,
+    pthid: Option<String>,
+    headers: BTreeMap<String, Value>,
+    created_time_now: bool,
+    clock: Option<Arc<dyn Clock + Send + Sync>>,
+    id_generator: Option<Arc<dyn IdGenerator + Send + Sync>>,
     created_time: Option<u64>,
     expires_time: Option<u64>,
+    max_ttl: Option<u64>,
     media_items_referenced: Vec<MediaItemReferenced>,
     media_items_inlined: Vec<MediaItemInlined>,
     id: Option<String>,
@@ -37,23 +44,33 @@ impl MediaItemsMessageBuilder {
 
     /// Builds the DIDComm message.
     pub fn build(self) -> Result<Message, MessageBuilderError> {
-        let type_ = "https://didcomm.org/media-sharing/1.0/share-media";
-        let id = self.id.clone().unwrap_or(Uuid::new_v4().to_string());
+        let type_ = MEDIA_ITEMS_MESSAGE_TYPE;
+        let id = self.id.clone().unwrap_or_else(|| self.next_id());
 
         if self.media_items_inlined.is_empty() && self.media_items_referenced.is_empty() {
             return Err(MessageBuilderError::MissingMediaItem);
         }
 
-        let value_media_items_inlined = self
+        let media_items_inlined = self
             .media_items_inlined
             .iter()
-            .map(|item| item.to_body_item())
+            .cloned()
+            .map(MediaItemInlined::with_checked_content)
             .collect::<Result<Vec<_>, MessageBuilderError>>()?;
 
+        let value_media_items_inlined = media_items_inlined
+            .iter()
+            .map(|item| item.to_body_item(self.next_id()))
+            .collect::<Result<Vec<_>, MessageBuilderError>>()?;
+
+        for media_item in &self.media_items_referenced {
+            media_item.check()?;
+        }
+
         let value_media_items_referenced = self
             .media_items_referenced
             .iter()
-            .map(|item| item.to_body_item())
+            .map(|item| item.to_body_item(self.next_id()))
             .collect::<Result<Vec<_>, MessageBuilderError>>()?;
 
         let body = serde_json::json!({
@@ -66,7 +83,7 @@ impl MediaItemsMessageBuilder {
         let mut didcomm_msg_builder = Message::build(id, type_.into(), body);
         didcomm_msg_builder = self.add_common_to_builder(didcomm_msg_builder)?;
 
-        for media_item in self.media_items_inlined {
+        for media_item in media_items_inlined {
             didcomm_msg_builder = didcomm_msg_builder.attachment(media_item.into_attachment());
         }
 
@@ -2,16 +2,18 @@ use alloc::string::{String, ToString};
 use didcomm::{Attachment, LinksAttachmentData};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use uuid::Uuid;
 
-use crate::error::MessageBuilderError;
+use crate::ciphering::Ciphering;
+use crate::error::{MessageBuilderError, MessageParserError};
+use crate::media_type::is_valid_media_type;
+use crate::multihash::{multihash, verify_multihash, HashAlgorithm};
 
 // / Represents a media item by reference in a DIDComm message.
 #[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct MediaItemReferenced {
     /// Attachment ID.
     pub id: String,
-    /// Media type of file
+    /// Media type of file, e.g. "image/png"
     pub media_type: String,
     /// File name
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -22,10 +24,21 @@ pub struct MediaItemReferenced {
     /// Reference
     pub link: String,
     /// The hash of the content encoded in multi-hash format. Used as an integrity check for the attachment.
+    /// `from_content` and `from_reader` compute it as base58btc encoded multihash.
     pub hash: String,
-    /// Encryption information
+    /// Encryption information of the content behind `link`, see `encrypt_content`.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub ciphering: Option<Value>,
+    pub ciphering: Option<Ciphering>,
+    /// Format of the file if the media type is not sufficient.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// Last modification time of the file, in seconds since the Unix epoch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lastmod_time: Option<u64>,
+    /// Size of the file in bytes, so receivers can show it before downloading.
+    /// Set by `from_content` and `from_reader`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub byte_count: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
@@ -35,22 +48,22 @@ pub(crate) struct MediaItemInBody {
     pub(crate) attachment_id: String,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub(crate) ciphering: Option<Value>,
+    pub(crate) ciphering: Option<Ciphering>,
 }
 
 pub trait MediaItemTrait {
-    fn to_body_item(&self) -> Result<Value, MessageBuilderError>;
+    fn to_body_item(&self, id: String) -> Result<Value, MessageBuilderError>;
     fn into_attachment(self) -> Attachment;
 }
 
 impl MediaItemTrait for MediaItemReferenced {
-    fn to_body_item(&self) -> Result<Value, MessageBuilderError> {
+    fn to_body_item(&self, id: String) -> Result<Value, MessageBuilderError> {
         serde_json::to_value(MediaItemInBody {
-            id: Uuid::new_v4().to_string(),
+            id,
             attachment_id: self.id.clone(),
             ciphering: self.ciphering.clone(),
         })
-        .map_err(|_| MessageBuilderError::SerializationError)
+        .map_err(|e| MessageBuilderError::SerializationError(e.to_string()))
     }
 
     fn into_attachment(self) -> Attachment {
@@ -66,9 +79,117 @@ impl MediaItemTrait for MediaItemReferenced {
             },
             description: self.description,
             filename: self.filename,
-            format: None,
-            lastmod_time: None,
-            byte_count: None,
+            format: self.format,
+            lastmod_time: self.lastmod_time,
+            byte_count: self.byte_count,
         }
     }
 }
+
+impl MediaItemReferenced {
+    /// Creates a media item for `content` that is available at `link`,
+    /// with the multihash of the content computed with `algorithm`.
+    pub fn from_content(
+        id: String,
+        media_type: String,
+        link: String,
+        content: &[u8],
+        algorithm: HashAlgorithm,
+    ) -> Self {
+        MediaItemReferenced {
+            id,
+            media_type,
+            link,
+            hash: multihash(algorithm, content),
+            byte_count: Some(content.len() as u64),
+            ..Default::default()
+        }
+    }
+
+    /// Creates a media item for the content read from `reader`, see `from_content`.
+    /// The content is hashed while it is read, without holding it in memory.
+    #[cfg(feature = "std")]
+    pub fn from_reader(
+        id: String,
+        media_type: String,
+        link: String,
+        mut reader: impl std::io::Read,
+        algorithm: HashAlgorithm,
+    ) -> std::io::Result<Self> {
+        let mut hasher = crate::multihash::MultihashHasher::new(algorithm);
+        let byte_count = std::io::copy(&mut reader, &mut hasher)?;
+        Ok(MediaItemReferenced {
+            id,
+            media_type,
+            link,
+            hash: hasher.finalize(),
+            byte_count: Some(byte_count),
+            ..Default::default()
+        })
+    }
+
+    /// Checks the syntax of `media_type` and the parameters of `ciphering`. The content is not
+    /// available to sniff, so the media type of a referenced item cannot be filled in.
+    pub(crate) fn check(&self) -> Result<(), MessageBuilderError> {
+        if !is_valid_media_type(&self.media_type) {
+            return Err(MessageBuilderError::InvalidMediaType(
+                self.id.clone(),
+                self.media_type.clone(),
+            ));
+        }
+        match &self.ciphering {
+            Some(ciphering) => ciphering
+                .validate()
+                .map_err(|e| MessageBuilderError::InvalidCiphering(self.id.clone(), e)),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks that `content`, e.g. downloaded from `link`, matches the multihash in `hash`.
+    /// Returns `false` if `hash` is not a base58btc encoded multihash of a supported hash function.
+    pub fn verify_hash(&self, content: &[u8]) -> bool {
+        verify_multihash(&self.hash, content)
+    }
+
+    /// Rebuilds a referenced media item from the attachment it was serialized into
+    /// and the ciphering information of its body item.
+    pub(crate) fn from_attachment(
+        attachment: Attachment,
+        ciphering: Option<Ciphering>,
+    ) -> Result<Self, MessageParserError> {
+        let id = attachment
+            .id
+            .ok_or(MessageParserError::MissingAttachmentField("id"))?;
+        let (link, hash) = match attachment.data {
+            didcomm::AttachmentData::Links { value } => {
+                let link = value
+                    .links
+                    .into_iter()
+                    .next()
+                    .ok_or(MessageParserError::MissingAttachmentField("links"))?;
+                (link, value.hash)
+            }
+            _ => return Err(MessageParserError::UnsupportedAttachmentData(id)),
+        };
+        if let Some(ciphering) = &ciphering {
+            ciphering
+                .validate()
+                .map_err(|e| MessageParserError::InvalidCiphering(id.clone(), e))?;
+        }
+
+        Ok(MediaItemReferenced {
+            id,
+            media_type: attachment
+                .media_type
+                .ok_or(MessageParserError::MissingAttachmentField("media_type"))?,
+            filename: attachment.filename,
+            description: attachment.description,
+            link,
+            hash,
+            ciphering,
+            format: attachment.format,
+            lastmod_time: attachment.lastmod_time,
+            byte_count: attachment.byte_count,
+        })
+    }
+}
@@ -0,0 +1,163 @@
+use alloc::string::String;
+
+/// Media type of content whose type is not known.
+pub(crate) const OCTET_STREAM: &str = "application/octet-stream";
+
+/// Checks that `media_type` is a media type as defined in [RFC 6838](https://www.rfc-editor.org/rfc/rfc6838#section-4.2),
+/// e.g. "image/png", optionally followed by parameters such as "; charset=utf-8".
+pub(crate) fn is_valid_media_type(media_type: &str) -> bool {
+    let mut parts = media_type.split(';');
+    let essence = parts.next().unwrap_or_default();
+    let Some((type_, subtype)) = essence.split_once('/') else {
+        return false;
+    };
+    is_restricted_name(type_)
+        && is_restricted_name(subtype)
+        && parts.all(|parameter| {
+            let Some((name, value)) = parameter.trim_start().split_once('=') else {
+                return false;
+            };
+            is_restricted_name(name) && is_parameter_value(value)
+        })
+}
+
+/// Media types detected by `sniff_media_type`.
+const SNIFFED_MEDIA_TYPES: [&str; 6] = [
+    "image/png",
+    "image/jpeg",
+    "application/pdf",
+    "video/mp4",
+    "image/webp",
+    "application/zip",
+];
+
+/// Detects the media type of `content` from its magic bytes, for PNG, JPEG, PDF, MP4, WebP and ZIP files.
+pub(crate) fn sniff_media_type(content: &[u8]) -> Option<&'static str> {
+    if content.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if content.starts_with(b"\xff\xd8\xff") {
+        Some("image/jpeg")
+    } else if content.starts_with(b"%PDF-") {
+        Some("application/pdf")
+    } else if content.len() >= 12 && &content[4..8] == b"ftyp" {
+        Some("video/mp4")
+    } else if content.len() >= 12 && content.starts_with(b"RIFF") && &content[8..12] == b"WEBP" {
+        Some("image/webp")
+    } else if content.starts_with(b"PK\x03\x04") || content.starts_with(b"PK\x05\x06") {
+        Some("application/zip")
+    } else {
+        None
+    }
+}
+
+/// Checks that the declared `media_type` is compatible with the media type `sniffed` from the content,
+/// or with content without known magic bytes if `sniffed` is `None`.
+/// `application/octet-stream` makes no claim about the content and is always accepted.
+pub(crate) fn matches_sniffed_media_type(media_type: &str, sniffed: Option<&str>) -> bool {
+    let essence = essence(media_type);
+    if essence == OCTET_STREAM {
+        return true;
+    }
+    match sniffed {
+        Some(sniffed) => compatible_media_types(sniffed).contains(&essence.as_str()),
+        // Content that claims to be a sniffable format must carry its magic bytes.
+        None => !SNIFFED_MEDIA_TYPES
+            .iter()
+            .any(|sniffed| compatible_media_types(sniffed).contains(&essence.as_str())),
+    }
+}
+
+/// Media types of the formats stored in the container detected as `sniffed`, e.g. Office documents for ZIP
+/// or QuickTime videos for MP4.
+fn compatible_media_types(sniffed: &str) -> &'static [&'static str] {
+    match sniffed {
+        "image/png" => &["image/png"],
+        "image/jpeg" => &["image/jpeg", "image/jpg", "image/pjpeg"],
+        "application/pdf" => &["application/pdf"],
+        "video/mp4" => &[
+            "video/mp4",
+            "video/quicktime",
+            "video/3gpp",
+            "video/3gpp2",
+            "video/x-m4v",
+            "audio/mp4",
+            "audio/x-m4a",
+            "application/mp4",
+            "image/heic",
+            "image/heif",
+            "image/avif",
+        ],
+        "image/webp" => &["image/webp"],
+        "application/zip" => &[
+            "application/zip",
+            "application/x-zip-compressed",
+            "application/java-archive",
+            "application/epub+zip",
+            "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+            "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+            "application/vnd.oasis.opendocument.text",
+            "application/vnd.oasis.opendocument.spreadsheet",
+            "application/vnd.oasis.opendocument.presentation",
+            "application/vnd.android.package-archive",
+        ],
+        _ => &[],
+    }
+}
+
+/// Lowercase type and subtype of `media_type`, without parameters.
+fn essence(media_type: &str) -> String {
+    media_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+/// restricted-name = restricted-name-first *126restricted-name-chars
+fn is_restricted_name(name: &str) -> bool {
+    let bytes = name.as_bytes();
+    (1..=127).contains(&bytes.len())
+        && bytes[0].is_ascii_alphanumeric()
+        && bytes.iter().all(|b| {
+            b.is_ascii_alphanumeric()
+                || matches!(
+                    b,
+                    b'!' | b'#' | b'$' | b'&' | b'-' | b'^' | b'_' | b'.' | b'+'
+                )
+        })
+}
+
+/// Parameter value: a token or a quoted string, see [RFC 2045](https://www.rfc-editor.org/rfc/rfc2045#section-5.1).
+fn is_parameter_value(value: &str) -> bool {
+    if let Some(quoted) = value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+    {
+        return quoted
+            .bytes()
+            .all(|b| b != b'"' && (b == b'\t' || (b' '..=b'~').contains(&b)));
+    }
+    !value.is_empty()
+        && value.bytes().all(|b| {
+            b.is_ascii_graphic()
+                && !matches!(
+                    b,
+                    b'(' | b')'
+                        | b'<'
+                        | b'>'
+                        | b'@'
+                        | b','
+                        | b';'
+                        | b':'
+                        | b'\\'
+                        | b'"'
+                        | b'/'
+                        | b'['
+                        | b']'
+                        | b'?'
+                        | b'='
+                )
+        })
+}
@@ -0,0 +1,226 @@
+use alloc::{string::String, vec::Vec};
+use core::convert::TryFrom;
+use didcomm::{Attachment, Message};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    direct_message_builder::LANG_HEADER, error::MessageParserError,
+    language_tag::is_valid_language_tag, media_item_referenced::MediaItemInBody, JsonWebKey,
+    MediaItemInlined, MediaItemReferenced, DIRECT_MESSAGE_TYPE, KEY_SHARING_MESSAGE_TYPE,
+    MEDIA_ITEMS_MESSAGE_TYPE,
+};
+
+/// Direct message parsed from a DIDComm [Basic Message](https://didcomm.org/basicmessage/2.0/).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DirectMessage {
+    /// ID of the message.
+    pub id: String,
+    /// Creation time of the message.
+    pub created_time: Option<u64>,
+    /// Expiration time of the message.
+    pub expires_time: Option<u64>,
+    /// Recipients of the message.
+    pub to: Option<Vec<String>>,
+    /// Sender of the message.
+    pub from: Option<String>,
+    /// ID of the thread of the message.
+    pub thid: Option<String>,
+    /// ID of the parent thread of the message.
+    pub pthid: Option<String>,
+    /// Content of the message.
+    pub message: String,
+    /// Language of the content as a BCP 47 tag.
+    pub lang: Option<String>,
+}
+
+/// Key sharing message parsed from a DIDComm message.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct KeySharingMessage {
+    /// ID of the message.
+    pub id: String,
+    /// Creation time of the message.
+    pub created_time: Option<u64>,
+    /// Expiration time of the message.
+    pub expires_time: Option<u64>,
+    /// Recipients of the message.
+    pub to: Option<Vec<String>>,
+    /// Sender of the message.
+    pub from: Option<String>,
+    /// ID of the thread of the message.
+    pub thid: Option<String>,
+    /// ID of the parent thread of the message.
+    pub pthid: Option<String>,
+    /// Shared keys.
+    pub keys: Vec<JsonWebKey>,
+}
+
+/// Media items message parsed from a DIDComm [Media Sharing](https://didcomm.org/media-sharing/1.0/) message.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MediaItemsMessage {
+    /// ID of the message.
+    pub id: String,
+    /// Creation time of the message.
+    pub created_time: Option<u64>,
+    /// Expiration time of the message.
+    pub expires_time: Option<u64>,
+    /// Recipients of the message.
+    pub to: Option<Vec<String>>,
+    /// Sender of the message.
+    pub from: Option<String>,
+    /// ID of the thread of the message.
+    pub thid: Option<String>,
+    /// ID of the parent thread of the message.
+    pub pthid: Option<String>,
+    /// Media items, in the order of the body items.
+    pub media_items: Vec<MediaItem>,
+}
+
+/// Media item joined back with its attachment.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum MediaItem {
+    /// Media item whose content is behind a link.
+    Referenced(MediaItemReferenced),
+    /// Media item whose content is embedded in the message as base64.
+    Inlined(MediaItemInlined),
+}
+
+/// Message parsed according to its `type` header.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ParsedMessage {
+    /// Message created by `DirectMessageBuilder`.
+    Direct(DirectMessage),
+    /// Message created by `KeySharingMessageBuilder`.
+    KeySharing(KeySharingMessage),
+    /// Message created by `MediaItemsMessageBuilder`.
+    MediaItems(MediaItemsMessage),
+}
+
+/// Parses a DIDComm message created by one of the builders of this crate.
+/// The builder is recognised from the `type` header of the message.
+pub fn parse_message(msg: Message) -> Result<ParsedMessage, MessageParserError> {
+    match msg.type_.as_str() {
+        DIRECT_MESSAGE_TYPE => DirectMessage::try_from(msg).map(ParsedMessage::Direct),
+        KEY_SHARING_MESSAGE_TYPE => KeySharingMessage::try_from(msg).map(ParsedMessage::KeySharing),
+        MEDIA_ITEMS_MESSAGE_TYPE => MediaItemsMessage::try_from(msg).map(ParsedMessage::MediaItems),
+        _ => Err(MessageParserError::UnknownMessageType(msg.type_)),
+    }
+}
+
+impl TryFrom<Message> for DirectMessage {
+    type Error = MessageParserError;
+
+    fn try_from(msg: Message) -> Result<Self, Self::Error> {
+        check_type(&msg, DIRECT_MESSAGE_TYPE)?;
+        let message = match body_field(&msg, "content")? {
+            Value::String(content) => content.clone(),
+            _ => return Err(MessageParserError::InvalidBodyField("content")),
+        };
+        let lang = match msg.extra_headers.get(LANG_HEADER) {
+            None => None,
+            Some(Value::String(lang)) if is_valid_language_tag(lang) => Some(lang.clone()),
+            Some(_) => return Err(MessageParserError::InvalidHeader(LANG_HEADER)),
+        };
+
+        Ok(DirectMessage {
+            id: msg.id,
+            created_time: msg.created_time,
+            expires_time: msg.expires_time,
+            to: msg.to,
+            from: msg.from,
+            thid: msg.thid,
+            pthid: msg.pthid,
+            message,
+            lang,
+        })
+    }
+}
+
+impl TryFrom<Message> for KeySharingMessage {
+    type Error = MessageParserError;
+
+    fn try_from(msg: Message) -> Result<Self, Self::Error> {
+        check_type(&msg, KEY_SHARING_MESSAGE_TYPE)?;
+        let keys = serde_json::from_value(body_field(&msg, "keys")?.clone())
+            .map_err(|_| MessageParserError::InvalidBodyField("keys"))?;
+
+        Ok(KeySharingMessage {
+            id: msg.id,
+            created_time: msg.created_time,
+            expires_time: msg.expires_time,
+            to: msg.to,
+            from: msg.from,
+            thid: msg.thid,
+            pthid: msg.pthid,
+            keys,
+        })
+    }
+}
+
+impl TryFrom<Message> for MediaItemsMessage {
+    type Error = MessageParserError;
+
+    fn try_from(msg: Message) -> Result<Self, Self::Error> {
+        check_type(&msg, MEDIA_ITEMS_MESSAGE_TYPE)?;
+        let items: Vec<MediaItemInBody> =
+            serde_json::from_value(body_field(&msg, "items")?.clone())
+                .map_err(|_| MessageParserError::InvalidBodyField("items"))?;
+
+        let mut attachments = msg.attachments.unwrap_or_default();
+        let media_items = items
+            .into_iter()
+            .map(|item| {
+                let attachment = take_attachment(&mut attachments, &item.attachment_id)?;
+                match attachment.data {
+                    didcomm::AttachmentData::Base64 { .. } => {
+                        MediaItemInlined::from_attachment(attachment).map(MediaItem::Inlined)
+                    }
+                    didcomm::AttachmentData::Links { .. } => {
+                        MediaItemReferenced::from_attachment(attachment, item.ciphering)
+                            .map(MediaItem::Referenced)
+                    }
+                    didcomm::AttachmentData::Json { .. } => Err(
+                        MessageParserError::UnsupportedAttachmentData(item.attachment_id),
+                    ),
+                }
+            })
+            .collect::<Result<Vec<_>, MessageParserError>>()?;
+
+        Ok(MediaItemsMessage {
+            id: msg.id,
+            created_time: msg.created_time,
+            expires_time: msg.expires_time,
+            to: msg.to,
+            from: msg.from,
+            thid: msg.thid,
+            pthid: msg.pthid,
+            media_items,
+        })
+    }
+}
+
+fn check_type(msg: &Message, expected: &str) -> Result<(), MessageParserError> {
+    if msg.type_ != expected {
+        return Err(MessageParserError::UnknownMessageType(msg.type_.clone()));
+    }
+    Ok(())
+}
+
+fn body_field<'a>(msg: &'a Message, field: &'static str) -> Result<&'a Value, MessageParserError> {
+    msg.body
+        .get(field)
+        .ok_or(MessageParserError::MissingBodyField(field))
+}
+
+/// Removes the attachment referenced by a body item, so that attachments
+/// sharing the same ID are matched in order.
+fn take_attachment(
+    attachments: &mut Vec<Attachment>,
+    attachment_id: &str,
+) -> Result<Attachment, MessageParserError> {
+    let position = attachments
+        .iter()
+        .position(|attachment| attachment.id.as_deref() == Some(attachment_id))
+        .ok_or_else(|| MessageParserError::DanglingAttachmentId(attachment_id.into()))?;
+    Ok(attachments.remove(position))
+}
@@ -0,0 +1,162 @@
+use alloc::{string::String, vec::Vec};
+use blake2::{digest::consts::U32, Blake2b};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sha3::Sha3_256;
+
+/// Hash function of a [multihash](https://multiformats.io/multihash/).
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    /// SHA2-256.
+    #[default]
+    #[serde(rename = "sha2-256")]
+    Sha2_256,
+    /// BLAKE2b with a 256 bit digest.
+    #[serde(rename = "blake2b-256")]
+    Blake2b256,
+    /// SHA3-256.
+    #[serde(rename = "sha3-256")]
+    Sha3_256,
+}
+
+impl HashAlgorithm {
+    /// Multicodec code of the hash function.
+    pub fn code(&self) -> u64 {
+        match self {
+            HashAlgorithm::Sha2_256 => 0x12,
+            HashAlgorithm::Blake2b256 => 0xb220,
+            HashAlgorithm::Sha3_256 => 0x16,
+        }
+    }
+
+    fn from_code(code: u64) -> Option<Self> {
+        match code {
+            0x12 => Some(HashAlgorithm::Sha2_256),
+            0xb220 => Some(HashAlgorithm::Blake2b256),
+            0x16 => Some(HashAlgorithm::Sha3_256),
+            _ => None,
+        }
+    }
+}
+
+/// Incremental hasher computing the multihash of content that is read in chunks.
+/// With the `std` feature, it implements `std::io::Write`, e.g. for `std::io::copy`.
+#[derive(Debug, Clone)]
+pub struct MultihashHasher {
+    algorithm: HashAlgorithm,
+    state: HasherState,
+}
+
+#[derive(Debug, Clone)]
+enum HasherState {
+    Sha2_256(Sha256),
+    Blake2b256(Blake2b<U32>),
+    Sha3_256(Sha3_256),
+}
+
+impl MultihashHasher {
+    /// Creates a hasher for the given hash function.
+    pub fn new(algorithm: HashAlgorithm) -> Self {
+        let state = match algorithm {
+            HashAlgorithm::Sha2_256 => HasherState::Sha2_256(Sha256::new()),
+            HashAlgorithm::Blake2b256 => HasherState::Blake2b256(Blake2b::new()),
+            HashAlgorithm::Sha3_256 => HasherState::Sha3_256(Sha3_256::new()),
+        };
+        Self { algorithm, state }
+    }
+
+    /// Hashes the next chunk of the content.
+    pub fn update(&mut self, data: &[u8]) {
+        match &mut self.state {
+            HasherState::Sha2_256(hasher) => hasher.update(data),
+            HasherState::Blake2b256(hasher) => hasher.update(data),
+            HasherState::Sha3_256(hasher) => hasher.update(data),
+        }
+    }
+
+    /// Returns the multihash of the content, encoded as base58btc multibase ("z" prefix).
+    pub fn finalize(self) -> String {
+        encode_multihash(self.algorithm, &self.digest())
+    }
+
+    fn digest(self) -> Vec<u8> {
+        match self.state {
+            HasherState::Sha2_256(hasher) => hasher.finalize().to_vec(),
+            HasherState::Blake2b256(hasher) => hasher.finalize().to_vec(),
+            HasherState::Sha3_256(hasher) => hasher.finalize().to_vec(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::io::Write for MultihashHasher {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Computes the multihash of `content`, encoded as base58btc multibase ("z" prefix).
+pub fn multihash(algorithm: HashAlgorithm, content: &[u8]) -> String {
+    let mut hasher = MultihashHasher::new(algorithm);
+    hasher.update(content);
+    hasher.finalize()
+}
+
+/// Checks that `hash` is the multihash of `content`, using the hash function named in `hash`.
+/// Returns `false` if `hash` is not a base58btc encoded multihash of a supported hash function.
+pub fn verify_multihash(hash: &str, content: &[u8]) -> bool {
+    let Some((algorithm, digest)) = decode_multihash(hash) else {
+        return false;
+    };
+    let mut hasher = MultihashHasher::new(algorithm);
+    hasher.update(content);
+    hasher.digest() == digest
+}
+
+fn encode_multihash(algorithm: HashAlgorithm, digest: &[u8]) -> String {
+    let mut bytes = Vec::with_capacity(digest.len() + 4);
+    write_varint(&mut bytes, algorithm.code());
+    write_varint(&mut bytes, digest.len() as u64);
+    bytes.extend_from_slice(digest);
+    let mut encoded = String::from("z");
+    encoded.push_str(&bs58::encode(bytes).into_string());
+    encoded
+}
+
+fn decode_multihash(hash: &str) -> Option<(HashAlgorithm, Vec<u8>)> {
+    let bytes = bs58::decode(hash.strip_prefix('z')?).into_vec().ok()?;
+    let mut bytes = bytes.as_slice();
+    let algorithm = HashAlgorithm::from_code(read_varint(&mut bytes)?)?;
+    let length = read_varint(&mut bytes)?;
+    if bytes.len() as u64 != length {
+        return None;
+    }
+    Some((algorithm, bytes.to_vec()))
+}
+
+/// Appends `value` as an unsigned varint, as used by multiformats.
+fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+/// Reads an unsigned varint from the start of `bytes` and advances past it.
+fn read_varint(bytes: &mut &[u8]) -> Option<u64> {
+    let mut value = 0u64;
+    for (index, byte) in bytes.iter().enumerate().take(9) {
+        value |= u64::from(byte & 0x7f) << (7 * index);
+        if byte & 0x80 == 0 {
+            *bytes = &bytes[index + 1..];
+            return Some(value);
+        }
+    }
+    None
+}
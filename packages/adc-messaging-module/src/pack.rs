@@ -0,0 +1,74 @@
+use alloc::{string::String, vec::Vec};
+use didcomm::{Message, PackEncryptedOptions};
+
+use crate::{
+    did_resolver::{DidResolver, DidResolverAdapter},
+    error::EnvelopeError,
+    secrets_resolver::{InMemorySecretsResolver, SecretsResolver, SecretsResolverAdapter},
+};
+
+/// DIDComm encrypted message produced by [`pack_anoncrypt`] or [`pack_authcrypt`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackedMessage {
+    /// Encrypted message (JWE) as a JSON string.
+    pub message: String,
+    /// Key ID (DID URL) of the sender key used for encryption. `None` for anoncrypt.
+    pub from_kid: Option<String>,
+    /// Key IDs (DID URLs) of the recipient keys the message is encrypted for.
+    pub to_kids: Vec<String>,
+}
+
+/// Encrypts a message for `to` without authenticating the sender (anoncrypt).
+/// `to` is either a DID, to encrypt for all its key agreement keys, or a key ID.
+pub async fn pack_anoncrypt(
+    msg: &Message,
+    to: &str,
+    did_resolver: &dyn DidResolver,
+) -> Result<PackedMessage, EnvelopeError> {
+    // Anoncrypt never looks up sender secrets.
+    let secrets_resolver = InMemorySecretsResolver::new();
+    pack(msg, to, None, did_resolver, &secrets_resolver).await
+}
+
+/// Encrypts a message for `to` and authenticates the sender `from` (authcrypt).
+/// `to` and `from` are either DIDs or key IDs; when `from` is a DID, a sender key
+/// compatible with the recipient keys is selected.
+pub async fn pack_authcrypt(
+    msg: &Message,
+    to: &str,
+    from: &str,
+    did_resolver: &dyn DidResolver,
+    secrets_resolver: &dyn SecretsResolver,
+) -> Result<PackedMessage, EnvelopeError> {
+    pack(msg, to, Some(from), did_resolver, secrets_resolver).await
+}
+
+async fn pack(
+    msg: &Message,
+    to: &str,
+    from: Option<&str>,
+    did_resolver: &dyn DidResolver,
+    secrets_resolver: &dyn SecretsResolver,
+) -> Result<PackedMessage, EnvelopeError> {
+    // Messages are stored in buckets rather than sent through mediators.
+    let options = PackEncryptedOptions {
+        forward: false,
+        ..PackEncryptedOptions::default()
+    };
+    let (message, metadata) = msg
+        .pack_encrypted(
+            to,
+            from,
+            None,
+            &DidResolverAdapter(did_resolver),
+            &SecretsResolverAdapter(secrets_resolver),
+            &options,
+        )
+        .await?;
+
+    Ok(PackedMessage {
+        message,
+        from_kid: metadata.from_kid,
+        to_kids: metadata.to_kids,
+    })
+}
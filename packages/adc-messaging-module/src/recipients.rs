@@ -0,0 +1,19 @@
+use alloc::{vec, vec::Vec};
+use didcomm::Message;
+
+/// Splits a message with several recipients into copies addressed to a single recipient each,
+/// in the order of the `to` header, for delivering them separately.
+/// The copies keep the `id` and all other headers of the message.
+/// A message with at most one recipient is returned as is.
+pub fn split_by_recipient(message: &Message) -> Vec<Message> {
+    match message.to.as_deref() {
+        Some(to) if to.len() > 1 => to
+            .iter()
+            .map(|recipient| Message {
+                to: Some(vec![recipient.clone()]),
+                ..message.clone()
+            })
+            .collect(),
+        _ => vec![message.clone()],
+    }
+}
@@ -0,0 +1,132 @@
+use alloc::{
+    boxed::Box,
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use async_trait::async_trait;
+use didcomm::{
+    error::ErrorKind,
+    secrets::{Secret, SecretMaterial, SecretType},
+};
+use serde::Deserialize;
+
+use crate::{error::ResolverError, PrivateJwk};
+
+/// Looks up private keys by key ID (`kid`).
+/// Hands out the key material used to decrypt and to authenticate messages.
+#[async_trait(?Send)]
+pub trait SecretsResolver {
+    /// Gets the key with the given key ID. Returns `None` if the key is unknown.
+    async fn get_key(&self, kid: &str) -> Result<Option<PrivateJwk>, ResolverError>;
+
+    /// Returns the subset of the given key IDs for which a key is known.
+    async fn find_keys<'a>(&self, kids: &'a [&'a str]) -> Result<Vec<&'a str>, ResolverError>;
+}
+
+/// Secrets resolver over a set of keys held in memory, keyed by `kid`.
+#[derive(Debug, Clone, Default)]
+pub struct InMemorySecretsResolver {
+    keys: BTreeMap<String, PrivateJwk>,
+}
+
+impl InMemorySecretsResolver {
+    /// Creates an empty resolver.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a key, replacing any key with the same `kid`.
+    pub fn add_key(mut self, key: PrivateJwk) -> Self {
+        self.insert_key(key);
+        self
+    }
+
+    /// Adds a key in place, replacing any key with the same `kid`.
+    pub fn insert_key(&mut self, key: PrivateJwk) {
+        self.keys.insert(key.kid.clone(), key);
+    }
+
+    /// Removes the key with the given `kid`. Returns whether a key was removed.
+    pub fn remove_key(&mut self, kid: &str) -> bool {
+        self.keys.remove(kid).is_some()
+    }
+
+    /// Returns whether a key with the given `kid` is known.
+    pub fn contains_key(&self, kid: &str) -> bool {
+        self.keys.contains_key(kid)
+    }
+
+    /// Creates a resolver from a JSON array of keys or a [JWK set](https://datatracker.ietf.org/doc/html/rfc7517#section-5),
+    /// e.g. the contents of a key file.
+    pub fn from_json(json: &str) -> Result<Self, ResolverError> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Keys {
+            Set { keys: Vec<PrivateJwk> },
+            List(Vec<PrivateJwk>),
+        }
+
+        let keys = match serde_json::from_str(json)
+            .map_err(|e| ResolverError::InvalidKey(e.to_string()))?
+        {
+            Keys::Set { keys } => keys,
+            Keys::List(keys) => keys,
+        };
+        Ok(keys.into_iter().fold(Self::new(), Self::add_key))
+    }
+}
+
+impl From<Vec<PrivateJwk>> for InMemorySecretsResolver {
+    fn from(keys: Vec<PrivateJwk>) -> Self {
+        keys.into_iter().fold(Self::new(), Self::add_key)
+    }
+}
+
+#[async_trait(?Send)]
+impl SecretsResolver for InMemorySecretsResolver {
+    async fn get_key(&self, kid: &str) -> Result<Option<PrivateJwk>, ResolverError> {
+        Ok(self.keys.get(kid).cloned())
+    }
+
+    async fn find_keys<'a>(&self, kids: &'a [&'a str]) -> Result<Vec<&'a str>, ResolverError> {
+        Ok(kids
+            .iter()
+            .copied()
+            .filter(|kid| self.keys.contains_key(*kid))
+            .collect())
+    }
+}
+
+/// Exposes a [`SecretsResolver`] to the didcomm crate.
+pub(crate) struct SecretsResolverAdapter<'a>(pub(crate) &'a dyn SecretsResolver);
+
+#[async_trait(?Send)]
+impl didcomm::secrets::SecretsResolver for SecretsResolverAdapter<'_> {
+    async fn get_secret(&self, secret_id: &str) -> didcomm::error::Result<Option<Secret>> {
+        let key = self.0.get_key(secret_id).await.map_err(to_didcomm_error)?;
+        key.map(|key| {
+            // didcomm takes the JWK as a JSON value and owns it from here on; `key` itself is
+            // zeroized when it is dropped at the end of this closure.
+            let private_key_jwk = serde_json::to_value(&key)
+                .map_err(|e| didcomm::error::Error::msg(ErrorKind::InvalidState, e.to_string()))?;
+            Ok(Secret {
+                id: key.kid.clone(),
+                type_: SecretType::JsonWebKey2020,
+                secret_material: SecretMaterial::JWK { private_key_jwk },
+            })
+        })
+        .transpose()
+    }
+
+    async fn find_secrets<'a>(
+        &self,
+        secret_ids: &'a [&'a str],
+    ) -> didcomm::error::Result<Vec<&'a str>> {
+        self.0.find_keys(secret_ids).await.map_err(to_didcomm_error)
+    }
+}
+
+fn to_didcomm_error(e: ResolverError) -> didcomm::error::Error {
+    didcomm::error::Error::msg(ErrorKind::SecretNotFound, e.to_string())
+}
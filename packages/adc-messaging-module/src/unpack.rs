@@ -0,0 +1,61 @@
+use alloc::{string::String, vec::Vec};
+use didcomm::{Message, UnpackOptions};
+
+use crate::{
+    did_resolver::{DidResolver, DidResolverAdapter},
+    error::EnvelopeError,
+    secrets_resolver::{SecretsResolver, SecretsResolverAdapter},
+};
+
+/// Plaintext message recovered by [`unpack`], with the security properties of its envelope.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnpackedMessage {
+    /// Plaintext message.
+    pub message: Message,
+    /// Whether the message was encrypted.
+    pub encrypted: bool,
+    /// Whether the sender was authenticated, either by authcrypt or by a signature.
+    pub authenticated: bool,
+    /// Whether the message was signed, providing non-repudiation.
+    pub signed: bool,
+    /// Key ID (DID URL) of the sender key used for authcrypt or for the signature.
+    pub from_kid: Option<String>,
+    /// Key IDs (DID URLs) of the recipient keys the message was encrypted for.
+    pub to_kids: Vec<String>,
+    /// DID of the authenticated sender. `None` for anonymous messages.
+    pub sender: Option<String>,
+}
+
+/// Unpacks a DIDComm encrypted (JWE), signed (JWS) or plaintext message.
+/// Recipient keys are looked up by `kid` through `secrets_resolver`,
+/// and sender keys are resolved through `did_resolver` to authenticate the sender.
+pub async fn unpack(
+    msg: &str,
+    did_resolver: &dyn DidResolver,
+    secrets_resolver: &dyn SecretsResolver,
+) -> Result<UnpackedMessage, EnvelopeError> {
+    let (message, metadata) = Message::unpack(
+        msg,
+        &DidResolverAdapter(did_resolver),
+        &SecretsResolverAdapter(secrets_resolver),
+        &UnpackOptions::default(),
+    )
+    .await?;
+
+    let from_kid = metadata.encrypted_from_kid.or(metadata.sign_from);
+    let authenticated = metadata.authenticated;
+    let sender = from_kid
+        .as_deref()
+        .filter(|_| authenticated)
+        .map(|kid| kid.split('#').next().unwrap_or(kid).into());
+
+    Ok(UnpackedMessage {
+        message,
+        encrypted: metadata.encrypted,
+        authenticated,
+        signed: metadata.non_repudiation,
+        from_kid,
+        to_kids: metadata.encrypted_to_kids.unwrap_or_default(),
+        sender,
+    })
+}
@@ -0,0 +1,225 @@
+use didcomm_module::{
+    decrypt_content, encrypt_content, CipherAlgorithm, Ciphering, CipheringError,
+    CipheringParameters,
+};
+use rand::{rngs::StdRng, SeedableRng};
+use serde_json::json;
+
+const CONTENT: &[u8] = b"hello world";
+
+fn parameters(iv: &str, key: &str) -> CipheringParameters {
+    CipheringParameters {
+        iv: iv.to_string(),
+        key: key.to_string(),
+    }
+}
+
+#[test]
+pub fn encrypt_and_decrypt() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for (algorithm, iv_length) in [
+        (CipherAlgorithm::Aes256Gcm, 24),
+        (CipherAlgorithm::XChaCha20Poly1305, 48),
+    ] {
+        let encrypted = encrypt_content(algorithm, CONTENT, &mut rng).unwrap();
+        let parameters = encrypted.ciphering.parameters().unwrap();
+        assert_eq!(encrypted.ciphering.algorithm(), Some(algorithm));
+        assert_eq!(parameters.iv.len(), iv_length);
+        assert_eq!(parameters.key.len(), 64);
+        assert_eq!(encrypted.ciphertext.len(), CONTENT.len() + 16);
+        assert_ne!(&encrypted.ciphertext[..CONTENT.len()], CONTENT);
+
+        let decrypted = decrypt_content(&encrypted.ciphertext, &encrypted.ciphering).unwrap();
+        assert_eq!(decrypted, CONTENT);
+    }
+}
+
+#[test]
+pub fn fresh_key_for_each_content() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let first = encrypt_content(CipherAlgorithm::Aes256Gcm, CONTENT, &mut rng).unwrap();
+    let second = encrypt_content(CipherAlgorithm::Aes256Gcm, CONTENT, &mut rng).unwrap();
+    assert_ne!(first.ciphering, second.ciphering);
+    assert_ne!(first.ciphertext, second.ciphertext);
+}
+
+#[test]
+pub fn decrypt_aes_gcm_test_vector() {
+    // NIST GCM test case 14: zero key, IV and plaintext.
+    let ciphering = Ciphering::Aes256Gcm(parameters(&"00".repeat(12), &"00".repeat(32)));
+    let ciphertext = [
+        0xce, 0xa7, 0x40, 0x3d, 0x4d, 0x60, 0x6b, 0x6e, 0x07, 0x4e, 0xc5, 0xd3, 0xba, 0xf3, 0x9d,
+        0x18, 0xd0, 0xd1, 0xc8, 0xa7, 0x99, 0x99, 0x6b, 0xf0, 0x26, 0x5b, 0x98, 0xb5, 0xd4, 0x8a,
+        0xb9, 0x19,
+    ];
+    assert_eq!(decrypt_content(&ciphertext, &ciphering).unwrap(), [0u8; 16]);
+}
+
+#[test]
+pub fn detects_modified_ciphertext() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut encrypted =
+        encrypt_content(CipherAlgorithm::XChaCha20Poly1305, CONTENT, &mut rng).unwrap();
+    encrypted.ciphertext[0] ^= 1;
+    assert_eq!(
+        decrypt_content(&encrypted.ciphertext, &encrypted.ciphering),
+        Err(CipheringError::DecryptionFailed)
+    );
+}
+
+#[test]
+pub fn wrong_algorithm_or_key() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let encrypted = encrypt_content(CipherAlgorithm::Aes256Gcm, CONTENT, &mut rng).unwrap();
+    let iv = &encrypted.ciphering.parameters().unwrap().iv;
+
+    let ciphering = Ciphering::Aes256Gcm(parameters(iv, &"11".repeat(32)));
+    assert_eq!(
+        decrypt_content(&encrypted.ciphertext, &ciphering),
+        Err(CipheringError::DecryptionFailed)
+    );
+
+    let ciphering = Ciphering::XChaCha20Poly1305(encrypted.ciphering.parameters().unwrap().clone());
+    assert_eq!(
+        decrypt_content(&encrypted.ciphertext, &ciphering),
+        Err(CipheringError::InvalidParameter("iv"))
+    );
+}
+
+#[test]
+pub fn invalid_parameters() {
+    for (iv, key, parameter) in [
+        ("00".repeat(11), "00".repeat(32), "iv"),
+        ("zz".repeat(12), "00".repeat(32), "iv"),
+        ("00".repeat(12), "00".repeat(16), "key"),
+        ("00".repeat(12), "0".repeat(63), "key"),
+    ] {
+        let ciphering = Ciphering::Aes256Gcm(parameters(&iv, &key));
+        assert_eq!(
+            decrypt_content(&[0; 32], &ciphering),
+            Err(CipheringError::InvalidParameter(parameter))
+        );
+    }
+}
+
+#[test]
+pub fn validate_parameters() {
+    let key = "00".repeat(32);
+    assert!(Ciphering::Aes256Gcm(parameters(&"00".repeat(12), &key))
+        .validate()
+        .is_ok());
+    assert!(
+        Ciphering::XChaCha20Poly1305(parameters(&"00".repeat(24), &key))
+            .validate()
+            .is_ok()
+    );
+    assert!(
+        Ciphering::Aes256Gcm(parameters(&format!("0x{}", "00".repeat(12)), &key))
+            .validate()
+            .is_ok()
+    );
+    assert!(
+        Ciphering::XChaCha20Poly1305(parameters(&"00".repeat(24), &format!("0x{}", key)))
+            .validate()
+            .is_ok()
+    );
+
+    assert_eq!(
+        Ciphering::XChaCha20Poly1305(parameters(&"00".repeat(12), &key)).validate(),
+        Err(CipheringError::InvalidParameter("iv"))
+    );
+    assert_eq!(
+        Ciphering::Aes256Gcm(parameters("test-iv", &key)).validate(),
+        Err(CipheringError::InvalidParameter("iv"))
+    );
+    assert_eq!(
+        Ciphering::Aes256Gcm(parameters(&"00".repeat(12), "")).validate(),
+        Err(CipheringError::InvalidParameter("key"))
+    );
+}
+
+#[test]
+pub fn validate_key() {
+    for key in [
+        "test-key".to_string(),
+        "00".repeat(16),
+        "00".repeat(33),
+        "0".repeat(63),
+        "zz".repeat(32),
+        // A key wrapped for the recipients can't be used by `decrypt_content`.
+        "eyJhbGciOiJFQ0RILUVTIn0...".to_string(),
+    ] {
+        for ciphering in [
+            Ciphering::Aes256Gcm(parameters(&"00".repeat(12), &key)),
+            Ciphering::XChaCha20Poly1305(parameters(&"00".repeat(24), &key)),
+        ] {
+            assert_eq!(
+                ciphering.validate(),
+                Err(CipheringError::InvalidParameter("key")),
+                "{}",
+                key
+            );
+        }
+    }
+}
+
+#[test]
+pub fn serialize_ciphering() {
+    let ciphering = Ciphering::XChaCha20Poly1305(parameters(&"00".repeat(24), &"00".repeat(32)));
+    let value = serde_json::to_value(&ciphering).unwrap();
+    assert_eq!(
+        value,
+        json!({
+            "algorithm": "XChaCha20-Poly1305",
+            "parameters": {
+                "iv": "00".repeat(24),
+                "key": "00".repeat(32)
+            }
+        })
+    );
+    assert_eq!(
+        serde_json::from_value::<Ciphering>(value).unwrap(),
+        ciphering
+    );
+}
+
+#[test]
+pub fn unknown_algorithm_fallback() {
+    for value in [
+        json!({ "alg": "aes-256-cbc" }),
+        json!({
+            "algorithm": "aes-256-cbc",
+            "parameters": {
+                "iv": "2f3849399c60cb04b923bd33265b81c7",
+                "key": "233f8ce4ac6aa125927ccd98af5750d08c9c61d98a3f5d43cbf096b4caaebe80"
+            }
+        }),
+    ] {
+        let ciphering: Ciphering = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(ciphering, Ciphering::Unknown(value.clone()));
+        assert_eq!(ciphering.algorithm(), None);
+        assert!(ciphering.validate().is_ok());
+        assert_eq!(serde_json::to_value(&ciphering).unwrap(), value);
+    }
+
+    let ciphering: Ciphering =
+        serde_json::from_value(json!({ "algorithm": "aes-256-cbc" })).unwrap();
+    assert_eq!(
+        decrypt_content(&[0; 32], &ciphering),
+        Err(CipheringError::UnsupportedAlgorithm(
+            "aes-256-cbc".to_string()
+        ))
+    );
+}
+
+#[test]
+pub fn malformed_supported_algorithm() {
+    let ciphering: Ciphering =
+        serde_json::from_value(json!({ "algorithm": "AES-GCM", "parameters": { "iv": 1 } }))
+            .unwrap();
+    assert!(matches!(ciphering, Ciphering::Unknown(_)));
+    assert_eq!(
+        ciphering.validate(),
+        Err(CipheringError::InvalidParameter("parameters"))
+    );
+}
@@ -0,0 +1,50 @@
+//! Fixtures shared by the tests, included with `#[path = "common/mod.rs"] mod common;`.
+#![allow(dead_code)]
+
+use didcomm_module::{
+    didcomm::{
+        secrets::{Secret, SecretMaterial},
+        test_vectors::{ALICE_DID, ALICE_SECRET_KEY_AGREEMENT_KEY_P256, BOB_DID},
+        Message,
+    },
+    DirectMessageBuilder, PrivateJwk, PublicJwk,
+};
+
+pub const BOB_KID: &str = "did:example:bob#key-p256-1";
+
+/// Direct message from Alice to Bob.
+pub fn message() -> Message {
+    DirectMessageBuilder::new()
+        .id("message-id".to_string())
+        .from(ALICE_DID.to_string())
+        .to(BOB_DID.to_string())
+        .message("Hello Bob.".to_string())
+        .build()
+        .unwrap()
+}
+
+/// Private key of a didcomm test vector secret.
+pub fn key(secret: &Secret) -> PrivateJwk {
+    let SecretMaterial::JWK { private_key_jwk } = &secret.secret_material else {
+        panic!("expected a JWK secret");
+    };
+    let public = PublicJwk {
+        kty: private_key_jwk["kty"].as_str().unwrap().into(),
+        crv: private_key_jwk["crv"].as_str().unwrap().into(),
+        x: private_key_jwk["x"].as_str().unwrap().into(),
+        y: private_key_jwk["y"].as_str().map(Into::into),
+        use_: "enc".into(),
+        kid: secret.id.clone(),
+    };
+    PrivateJwk::new(public, private_key_jwk["d"].as_str().unwrap().into())
+}
+
+/// Alice's P-256 key agreement key under the key ID `kid`.
+pub fn key_with_kid(kid: &str) -> PrivateJwk {
+    let (public, d) = key(&ALICE_SECRET_KEY_AGREEMENT_KEY_P256).into_parts();
+    let public = PublicJwk {
+        kid: kid.into(),
+        ..public
+    };
+    PrivateJwk::new(public, d.as_str().into())
+}
@@ -0,0 +1,66 @@
+use didcomm_module::{
+    didcomm::test_vectors::{ALICE_DID, ALICE_DID_DOC, BOB_DID},
+    DidResolver, InMemoryDidResolver, ResolverError,
+};
+use serde_json::json;
+
+#[test]
+pub fn resolve_known_did() {
+    let resolver = InMemoryDidResolver::new().add_did_doc(ALICE_DID_DOC.clone());
+
+    let did_doc = pollster::block_on(resolver.resolve(ALICE_DID)).unwrap();
+    assert_eq!(did_doc.unwrap().id, ALICE_DID);
+}
+
+#[test]
+pub fn resolve_unknown_did() {
+    let resolver = InMemoryDidResolver::new().add_did_doc(ALICE_DID_DOC.clone());
+
+    let did_doc = pollster::block_on(resolver.resolve(BOB_DID)).unwrap();
+    assert!(did_doc.is_none());
+}
+
+#[test]
+pub fn from_json() {
+    let did_docs = json!([
+        {
+            "id": "did:example:alice",
+            "keyAgreement": ["did:example:alice#key-x25519-1"],
+            "verificationMethod": [
+                {
+                    "id": "did:example:alice#key-x25519-1",
+                    "type": "JsonWebKey2020",
+                    "controller": "did:example:alice#key-x25519-1",
+                    "publicKeyJwk": {
+                        "crv": "X25519",
+                        "kty": "OKP",
+                        "x": "avH0O2Y4tqLAq8y9zpianr8ajii5m4F_mICrzNlatXs"
+                    }
+                }
+            ]
+        },
+        {
+            "id": "did:example:bob"
+        }
+    ]);
+    let resolver = InMemoryDidResolver::from_json(&did_docs.to_string()).unwrap();
+
+    let alice = pollster::block_on(resolver.resolve("did:example:alice"))
+        .unwrap()
+        .unwrap();
+    assert_eq!(alice.key_agreement, vec!["did:example:alice#key-x25519-1"]);
+    assert_eq!(alice.verification_method.len(), 1);
+    assert!(alice.service.is_empty());
+
+    let bob = pollster::block_on(resolver.resolve("did:example:bob")).unwrap();
+    assert!(bob.is_some());
+}
+
+#[test]
+pub fn from_json_invalid_document() {
+    let resolver = InMemoryDidResolver::from_json(r#"{"keyAgreement": []}"#);
+    assert!(matches!(
+        resolver.unwrap_err(),
+        ResolverError::InvalidDidDocument(_)
+    ));
+}
@@ -0,0 +1,81 @@
+use didcomm_module::{DirectMessageBuilder, MessageBuilderError};
+
+fn build_to(did: &str) -> Result<(), MessageBuilderError> {
+    DirectMessageBuilder::new()
+        .to(did.to_string())
+        .message("Hello World.".to_string())
+        .build()
+        .map(|_| ())
+}
+
+#[test]
+pub fn valid_dids() {
+    for did in [
+        "did:example:alice",
+        "did:example:alice#key-x25519-1",
+        "did:example:a:b%20c",
+        "did:kilt:4pnfkRn5UurBJTW92d9TaVLR2CqJdY4z5HPjrEbpGyBykare",
+        "did:kilt:4rDeMGr3Hi4NfxRUp8qVyhvgW3BSUBLneQisGa9ASkhh2sXB#0x1234",
+        "did:kilt:light:004pnfkRn5UurBJTW92d9TaVLR2CqJdY4z5HPjrEbpGyBykare",
+        "did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK",
+        "did:key:z6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc",
+        "did:key:zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169",
+        "did:key:zQ3shokFTS3brHcDQrn82RUDfCZESWL1ZdCEJwekUDPQiYBme",
+        "did:web:example.com",
+        "did:web:localhost%3A8443:user:alice",
+        "did:peer:0z6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V",
+        "did:peer:2.Ez6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc.Vz6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V.SeyJ0IjoiZG0iLCJzIjoiaHR0cHM6Ly9leGFtcGxlLmNvbS9lbmRwb2ludCIsInIiOlsiZGlkOmV4YW1wbGU6c29tZW1lZGlhdG9yI3NvbWVrZXkiXSwiYSI6WyJkaWRjb21tL3YyIiwiZGlkY29tbS9haXAyO2Vudj1yZmM1ODciXX0",
+    ] {
+        assert!(build_to(did).is_ok(), "{} should be valid", did);
+    }
+}
+
+#[test]
+pub fn invalid_dids() {
+    for did in [
+        "",
+        // Polkadot address without a DID method.
+        "4pnfkRn5UurBJTW92d9TaVLR2CqJdY4z5HPjrEbpGyBykare",
+        "did:",
+        "did:example",
+        "did:example:",
+        "did:Example:alice",
+        "did:example:alice:",
+        "did:example:ali ce",
+        "did:example:alice%2",
+        "did:example:alice#key 1",
+        // Checksum mismatch.
+        "did:kilt:4pnfkRn5UurBJTW92d9TaVLR2CqJdY4z5HPjrEbpGyBykara",
+        // Polkadot (prefix 0) address.
+        "did:kilt:15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5",
+        "did:kilt:light:4pnfkRn5UurBJTW92d9TaVLR2CqJdY4z5HPjrEbpGyBykare",
+        "did:key:6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK",
+        "did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2do",
+        "did:key:zInvalid0",
+        "did:web:-example.com",
+        "did:web:example..com",
+        "did:web:example.com%3A",
+        "did:web:example.com::alice",
+        "did:peer:5z6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V",
+        "did:peer:2",
+        "did:peer:2.Xz6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V",
+    ] {
+        assert!(
+            matches!(build_to(did), Err(MessageBuilderError::InvalidDid(value)) if value == did),
+            "{} should be invalid",
+            did
+        );
+    }
+}
+
+#[test]
+pub fn invalid_sender_did() {
+    let msg = DirectMessageBuilder::new()
+        .from("4pnfkRn5UurBJTW92d9TaVLR2CqJdY4z5HPjrEbpGyBykare".to_string())
+        .message("Hello World.".to_string())
+        .build();
+    assert!(matches!(
+        msg.unwrap_err(),
+        MessageBuilderError::InvalidDid(did) if did == "4pnfkRn5UurBJTW92d9TaVLR2CqJdY4z5HPjrEbpGyBykare"
+    ));
+}
@@ -1,4 +1,4 @@
-use didcomm_module::{DirectMessageBuilder, MessageBuilderError};
+use didcomm_module::{DirectMessageBuilder, FixedClock, MessageBuilderError, DEFAULT_MAX_TTL};
 use serde_json::json;
 
 #[test]
@@ -31,3 +31,259 @@ pub fn direct_message_no_message() {
         MessageBuilderError::MissingMessage
     ));
 }
+
+#[test]
+pub fn direct_message_thread() {
+    let msg = DirectMessageBuilder::new()
+        .id("message-id".to_string())
+        .thid("thread-id".to_string())
+        .pthid("parent-thread-id".to_string())
+        .message("Hello World.".to_string())
+        .build()
+        .unwrap();
+
+    assert_eq!(msg.thid.as_deref(), Some("thread-id"));
+    assert_eq!(msg.pthid.as_deref(), Some("parent-thread-id"));
+}
+
+#[test]
+pub fn direct_message_reply_to() {
+    let parent = DirectMessageBuilder::new()
+        .id("parent-id".to_string())
+        .from("did:example:alice".to_string())
+        .to("did:example:bob".to_string())
+        .message("Hello Bob.".to_string())
+        .build()
+        .unwrap();
+
+    let reply = DirectMessageBuilder::reply_to(&parent)
+        .id("reply-id".to_string())
+        .message("Hello Alice.".to_string())
+        .build()
+        .unwrap();
+    assert_eq!(reply.thid.as_deref(), Some("parent-id"));
+    assert_eq!(reply.from.as_deref(), Some("did:example:bob"));
+    assert_eq!(reply.to, Some(vec!["did:example:alice".to_string()]));
+
+    let second_reply = DirectMessageBuilder::reply_to(&reply)
+        .message("Hello again.".to_string())
+        .build()
+        .unwrap();
+    assert_eq!(second_reply.thid.as_deref(), Some("parent-id"));
+    assert_eq!(second_reply.from.as_deref(), Some("did:example:alice"));
+}
+
+#[test]
+pub fn direct_message_lang() {
+    let msg = DirectMessageBuilder::new()
+        .id("message-id".to_string())
+        .lang("de-CH".to_string())
+        .message("Grüezi.".to_string())
+        .build()
+        .unwrap();
+
+    let value = serde_json::to_value(msg).unwrap();
+    assert_eq!(value["lang"], "de-CH");
+}
+
+#[test]
+pub fn direct_message_language_tags() {
+    for lang in [
+        "en",
+        "EN-us",
+        "zh-Hant-TW",
+        "sr-Latn-RS",
+        "zh-yue-HK",
+        "es-419",
+        "sl-rozaj-biske",
+        "de-CH-1901",
+        "en-a-bbb-x-a-ccc",
+        "x-private",
+    ] {
+        let msg = DirectMessageBuilder::new()
+            .lang(lang.to_string())
+            .message("Hello.".to_string())
+            .build();
+        assert!(msg.is_ok(), "{} should be valid", lang);
+    }
+
+    for lang in [
+        "",
+        "e",
+        "en-",
+        "en_US",
+        "123",
+        "en-a",
+        "en-x",
+        "toolongtag",
+        "de-419-DE",
+        "en-US-x-",
+    ] {
+        let msg = DirectMessageBuilder::new()
+            .lang(lang.to_string())
+            .message("Hello.".to_string())
+            .build();
+        assert!(
+            matches!(msg.unwrap_err(), MessageBuilderError::InvalidLanguageTag(tag) if tag == lang),
+            "{} should be invalid",
+            lang
+        );
+    }
+}
+
+#[test]
+pub fn direct_message_headers() {
+    let msg = DirectMessageBuilder::new()
+        .id("message-id".to_string())
+        .header("bucket_id".to_string(), json!(7))
+        .header("tags".to_string(), json!(["chat"]))
+        .message("Hello World.".to_string())
+        .build()
+        .unwrap();
+
+    let value = serde_json::to_value(msg).unwrap();
+    assert_eq!(value["bucket_id"], 7);
+    assert_eq!(value["tags"], json!(["chat"]));
+}
+
+#[test]
+pub fn direct_message_reserved_header() {
+    for name in ["id", "thid", "created_time", "lang"] {
+        let msg = DirectMessageBuilder::new()
+            .header(name.to_string(), json!("value"))
+            .message("Hello World.".to_string())
+            .build();
+        assert!(matches!(
+            msg.unwrap_err(),
+            MessageBuilderError::ReservedHeader(header) if header == name
+        ));
+    }
+}
+
+#[test]
+pub fn direct_message_reserved_header_mixed_case() {
+    for name in ["Type", "FROM", "Expires_Time"] {
+        let msg = DirectMessageBuilder::new()
+            .header(name.to_string(), json!("value"))
+            .message("Hello World.".to_string())
+            .build();
+        assert!(matches!(
+            msg.unwrap_err(),
+            MessageBuilderError::ReservedHeader(header) if header == name
+        ));
+    }
+}
+
+#[test]
+pub fn direct_message_created_time_from_clock() {
+    let msg = DirectMessageBuilder::new()
+        .clock(FixedClock(1234))
+        .created_time(None)
+        .message("Hello World.".to_string())
+        .build()
+        .unwrap();
+
+    assert_eq!(msg.created_time, Some(1234));
+}
+
+#[test]
+pub fn direct_message_created_time_from_system_clock() {
+    let msg = DirectMessageBuilder::new()
+        .created_time(None)
+        .message("Hello World.".to_string())
+        .build()
+        .unwrap();
+
+    // 2024-01-01T00:00:00Z
+    assert!(msg.created_time.unwrap() > 1_704_067_200);
+}
+
+#[test]
+pub fn direct_message_explicit_created_time() {
+    let msg = DirectMessageBuilder::new()
+        .clock(FixedClock(1234))
+        .created_time(None)
+        .created_time(Some(42))
+        .message("Hello World.".to_string())
+        .build()
+        .unwrap();
+
+    assert_eq!(msg.created_time, Some(42));
+}
+
+#[test]
+pub fn direct_message_expires_time() {
+    let msg = DirectMessageBuilder::new()
+        .created_time(Some(1000))
+        .expires_time(2000)
+        .message("Hello World.".to_string())
+        .build()
+        .unwrap();
+    assert_eq!(msg.expires_time, Some(2000));
+
+    let msg = DirectMessageBuilder::new()
+        .created_time(Some(1000))
+        .expires_time(1000)
+        .message("Hello World.".to_string())
+        .build();
+    assert!(matches!(
+        msg.unwrap_err(),
+        MessageBuilderError::ExpiresBeforeCreated(1000)
+    ));
+}
+
+#[test]
+pub fn direct_message_expires_time_without_created_time() {
+    // Without created_time, the expiry time is checked against the current time of the clock.
+    let msg = DirectMessageBuilder::new()
+        .clock(FixedClock(1000))
+        .expires_time(2000)
+        .message("Hello World.".to_string())
+        .build()
+        .unwrap();
+    assert_eq!(msg.expires_time, Some(2000));
+
+    for expires_time in [5, 1000] {
+        let msg = DirectMessageBuilder::new()
+            .clock(FixedClock(1000))
+            .expires_time(expires_time)
+            .message("Hello World.".to_string())
+            .build();
+        assert!(matches!(
+            msg.unwrap_err(),
+            MessageBuilderError::ExpiresInPast(e) if e == expires_time
+        ));
+    }
+}
+
+#[test]
+pub fn direct_message_max_ttl() {
+    let msg = DirectMessageBuilder::new()
+        .created_time(Some(1000))
+        .expires_time(1000 + DEFAULT_MAX_TTL)
+        .message("Hello World.".to_string())
+        .build();
+    assert!(msg.is_ok());
+
+    let msg = DirectMessageBuilder::new()
+        .created_time(Some(1000))
+        .expires_time(1001 + DEFAULT_MAX_TTL)
+        .message("Hello World.".to_string())
+        .build();
+    assert!(matches!(
+        msg.unwrap_err(),
+        MessageBuilderError::ExpiresTooLate(_)
+    ));
+
+    // Without created_time, the TTL starts at the current time of the clock.
+    let msg = DirectMessageBuilder::new()
+        .clock(FixedClock(1000))
+        .max_ttl(60)
+        .expires_time(1061)
+        .message("Hello World.".to_string())
+        .build();
+    assert!(matches!(
+        msg.unwrap_err(),
+        MessageBuilderError::ExpiresTooLate(1061)
+    ));
+}
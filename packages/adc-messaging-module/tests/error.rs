@@ -0,0 +1,123 @@
+use std::{collections::HashSet, error::Error};
+
+use didcomm_module::{
+    CipheringError, DirectMessageBuilder, EnvelopeError, FreshnessError, KeyError,
+    MessageBuilderError, MessageParserError, ResolverError,
+};
+
+#[test]
+pub fn error_codes() {
+    let err = DirectMessageBuilder::new()
+        .to("not-a-did".to_string())
+        .message("Hello World.".to_string())
+        .build()
+        .unwrap_err();
+    assert_eq!(err.code(), "INVALID_DID");
+    assert_eq!(err.to_string(), "Invalid DID: not-a-did");
+
+    assert_eq!(
+        MessageBuilderError::MissingMessage.code(),
+        "MISSING_MESSAGE"
+    );
+    assert_eq!(FreshnessError::Expired(1000).code(), "MESSAGE_EXPIRED");
+}
+
+#[test]
+pub fn serialization_error_cause() {
+    let err = MessageBuilderError::SerializationError("key must be a string".to_string());
+    assert_eq!(
+        err.to_string(),
+        "Error during message serialization: key must be a string"
+    );
+}
+
+#[test]
+pub fn std_error() {
+    fn build() -> Result<(), Box<dyn std::error::Error>> {
+        DirectMessageBuilder::new().build()?;
+        Ok(())
+    }
+    assert_eq!(build().unwrap_err().to_string(), "Missing message content");
+}
+
+#[test]
+pub fn error_codes_are_unique() {
+    let codes = [
+        MessageBuilderError::MissingBody.code(),
+        MessageBuilderError::SerializationError(String::new()).code(),
+        MessageBuilderError::MissingKey.code(),
+        MessageBuilderError::MissingMessage.code(),
+        MessageBuilderError::MissingMediaItem.code(),
+        MessageBuilderError::InvalidKey(String::new()).code(),
+        MessageBuilderError::KidMismatch(String::new()).code(),
+        MessageBuilderError::UnsupportedKeyType(String::new(), String::new()).code(),
+        MessageBuilderError::UnsupportedCurve(String::new(), String::new()).code(),
+        MessageBuilderError::MissingKeyField(String::new(), "y").code(),
+        MessageBuilderError::UnexpectedKeyField(String::new(), "y").code(),
+        MessageBuilderError::InvalidKeyEncoding(String::new(), "x").code(),
+        MessageBuilderError::InvalidKeyLength(String::new(), "x").code(),
+        MessageBuilderError::InvalidKeyPoint(String::new()).code(),
+        MessageBuilderError::PrivateKeyMismatch(String::new()).code(),
+        MessageBuilderError::InvalidKeyUse(String::new(), String::new()).code(),
+        MessageBuilderError::InvalidLanguageTag(String::new()).code(),
+        MessageBuilderError::ReservedHeader(String::new()).code(),
+        MessageBuilderError::MissingClock.code(),
+        MessageBuilderError::ExpiresBeforeCreated(0).code(),
+        MessageBuilderError::ExpiresTooLate(0).code(),
+        MessageBuilderError::ExpiresInPast(0).code(),
+        MessageBuilderError::InvalidDid(String::new()).code(),
+        MessageBuilderError::InvalidBase64(String::new()).code(),
+        MessageBuilderError::InvalidMediaType(String::new(), String::new()).code(),
+        MessageBuilderError::MediaTypeMismatch(String::new(), String::new(), String::new()).code(),
+        MessageBuilderError::InvalidCiphering(String::new(), CipheringError::EncryptionFailed)
+            .code(),
+        MessageParserError::UnknownMessageType(String::new()).code(),
+        MessageParserError::MissingBodyField("body").code(),
+        MessageParserError::InvalidBodyField("body").code(),
+        MessageParserError::MissingAttachmentField("id").code(),
+        MessageParserError::DanglingAttachmentId(String::new()).code(),
+        MessageParserError::UnsupportedAttachmentData(String::new()).code(),
+        MessageParserError::InvalidHeader("header").code(),
+        MessageParserError::InvalidCiphering(String::new(), CipheringError::EncryptionFailed)
+            .code(),
+        FreshnessError::Expired(0).code(),
+        FreshnessError::CreatedInFuture(0).code(),
+        FreshnessError::ExpiresBeforeCreated(0).code(),
+        EnvelopeError::DidNotResolved(String::new()).code(),
+        EnvelopeError::DidUrlNotFound(String::new()).code(),
+        EnvelopeError::SecretNotFound(String::new()).code(),
+        EnvelopeError::NoCompatibleCrypto(String::new()).code(),
+        EnvelopeError::Unsupported(String::new()).code(),
+        EnvelopeError::Malformed(String::new()).code(),
+        EnvelopeError::Other(String::new()).code(),
+        ResolverError::InvalidDidDocument(String::new()).code(),
+        ResolverError::InvalidKey(String::new()).code(),
+        ResolverError::Other(String::new()).code(),
+        KeyError::GenerationFailed(String::new()).code(),
+        KeyError::UnsupportedKeyType(String::new()).code(),
+        KeyError::MissingField("y").code(),
+        CipheringError::EncryptionFailed.code(),
+        CipheringError::DecryptionFailed.code(),
+        CipheringError::InvalidParameter("iv").code(),
+        CipheringError::UnsupportedAlgorithm(String::new()).code(),
+    ];
+
+    let unique: HashSet<_> = codes.iter().collect();
+    assert_eq!(unique.len(), codes.len());
+}
+
+#[test]
+pub fn ciphering_error_source() {
+    let err = MessageParserError::InvalidCiphering(
+        "12345".to_string(),
+        CipheringError::InvalidParameter("iv"),
+    );
+    assert_eq!(
+        err.to_string(),
+        "Invalid ciphering of media item 12345: Invalid ciphering parameter: iv"
+    );
+    assert_eq!(
+        err.source().unwrap().to_string(),
+        "Invalid ciphering parameter: iv"
+    );
+}
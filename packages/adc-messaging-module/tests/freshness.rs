@@ -0,0 +1,53 @@
+use didcomm_module::{check_freshness, didcomm::Message, DirectMessageBuilder, FreshnessError};
+
+fn message(created_time: Option<u64>, expires_time: Option<u64>) -> Message {
+    let mut msg = DirectMessageBuilder::new()
+        .id("message-id".to_string())
+        .message("Hello World.".to_string())
+        .build()
+        .unwrap();
+    msg.created_time = created_time;
+    msg.expires_time = expires_time;
+    msg
+}
+
+#[test]
+pub fn fresh_message() {
+    let msg = message(Some(1000), Some(2000));
+    assert_eq!(check_freshness(&msg, 1000, 0), Ok(()));
+    assert_eq!(check_freshness(&msg, 1999, 0), Ok(()));
+    assert_eq!(check_freshness(&message(None, None), 5000, 0), Ok(()));
+}
+
+#[test]
+pub fn expired_message() {
+    let msg = message(Some(1000), Some(2000));
+    assert_eq!(
+        check_freshness(&msg, 2000, 0),
+        Err(FreshnessError::Expired(2000))
+    );
+    assert_eq!(check_freshness(&msg, 2000, 30), Ok(()));
+    assert_eq!(
+        check_freshness(&msg, 2030, 30),
+        Err(FreshnessError::Expired(2000))
+    );
+}
+
+#[test]
+pub fn message_created_in_future() {
+    let msg = message(Some(1000), None);
+    assert_eq!(
+        check_freshness(&msg, 900, 0),
+        Err(FreshnessError::CreatedInFuture(1000))
+    );
+    assert_eq!(check_freshness(&msg, 900, 100), Ok(()));
+}
+
+#[test]
+pub fn message_expires_before_created() {
+    let msg = message(Some(1000), Some(1000));
+    assert_eq!(
+        check_freshness(&msg, 1000, 0),
+        Err(FreshnessError::ExpiresBeforeCreated(1000))
+    );
+}
@@ -0,0 +1,70 @@
+use didcomm_module::{
+    DirectMessageBuilder, FixedClock, IdGenerator, RandomIdGenerator, SeededIdGenerator,
+    TimeOrderedIdGenerator,
+};
+use uuid::Uuid;
+
+#[test]
+pub fn random_id_generator() {
+    let generator = RandomIdGenerator;
+    let id = Uuid::parse_str(&generator.next_id()).unwrap();
+    assert_eq!(id.get_version_num(), 4);
+    assert_ne!(generator.next_id(), generator.next_id());
+}
+
+#[test]
+pub fn time_ordered_id_generator() {
+    // 2024-01-01T00:00:00Z
+    let generator = TimeOrderedIdGenerator::new(FixedClock(1_704_067_200));
+    let id = generator.next_id();
+    let uuid = Uuid::parse_str(&id).unwrap();
+    assert_eq!(uuid.get_version_num(), 7);
+    assert_eq!(uuid.get_variant(), Some(uuid::Variant::RFC4122));
+    assert_eq!(
+        &uuid.as_bytes()[..6],
+        &1_704_067_200_000u64.to_be_bytes()[2..]
+    );
+    assert_ne!(id, generator.next_id());
+}
+
+#[test]
+pub fn time_ordered_ids_sort_by_time() {
+    let earlier = TimeOrderedIdGenerator::new(FixedClock(1_704_067_200)).next_id();
+    let later = TimeOrderedIdGenerator::new(FixedClock(1_704_067_201)).next_id();
+    assert!(earlier < later);
+}
+
+#[test]
+pub fn seeded_id_generator() {
+    let generator = SeededIdGenerator::new(42);
+    let ids = [generator.next_id(), generator.next_id()];
+    assert_eq!(
+        ids,
+        [
+            "bdd73226-2feb-4e95-a8ef-e333b266f103",
+            "47526757-130f-4f52-981c-e1ff0e4ae394",
+        ]
+    );
+    assert_eq!(Uuid::parse_str(&ids[0]).unwrap().get_version_num(), 4);
+
+    let other = SeededIdGenerator::new(7);
+    assert_ne!(other.next_id(), ids[0]);
+}
+
+#[test]
+pub fn builder_id_generator() {
+    let msg = DirectMessageBuilder::new()
+        .id_generator(SeededIdGenerator::new(42))
+        .message("Hello World.".to_string())
+        .build()
+        .unwrap();
+    assert_eq!(msg.id, "bdd73226-2feb-4e95-a8ef-e333b266f103");
+
+    let msg = DirectMessageBuilder::new()
+        .id("message-id".to_string())
+        .id_generator(SeededIdGenerator::new(42))
+        .message("Hello World.".to_string())
+        .build()
+        .unwrap();
+    assert_eq!(msg.id, "message-id");
+}
@@ -0,0 +1,95 @@
+use didcomm_module::{JsonWebKey, KeyError, PrivateJwk, PublicJwk};
+
+#[test]
+pub fn thumbprint_okp() {
+    // RFC 8037, appendix A.3
+    let key = PublicJwk {
+        kty: "OKP".to_string(),
+        crv: "Ed25519".to_string(),
+        x: "11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo".into(),
+        y: None,
+        use_: "sig".into(),
+        kid: "".into(),
+    };
+    assert_eq!(
+        key.thumbprint().unwrap(),
+        "kPrK_qmxVWaYVA9wwBF6Iuo3vVzz7TxHCTwXBygrS4k"
+    );
+}
+
+#[test]
+pub fn thumbprint_ignores_private_key_and_metadata() {
+    let public = ec_key();
+    let private = PrivateJwk::new(
+        PublicJwk {
+            use_: "sig".into(),
+            kid: "key-id".into(),
+            ..public.clone()
+        },
+        "0002".into(),
+    );
+    assert_eq!(
+        JsonWebKey::from(private).thumbprint().unwrap(),
+        public.thumbprint().unwrap()
+    );
+}
+
+#[test]
+pub fn thumbprint_ec_missing_y() {
+    let key = PublicJwk {
+        y: None,
+        ..ec_key()
+    };
+    assert!(matches!(
+        key.thumbprint().unwrap_err(),
+        KeyError::MissingField("y")
+    ));
+}
+
+#[test]
+pub fn thumbprint_unsupported_key_type() {
+    let key = PublicJwk {
+        kty: "RSA".into(),
+        ..ec_key()
+    };
+    assert!(matches!(
+        key.thumbprint().unwrap_err(),
+        KeyError::UnsupportedKeyType(_)
+    ));
+}
+
+#[test]
+pub fn debug_redacts_private_key() {
+    let key = PrivateJwk::new(ec_key(), "private-key".into());
+
+    let debug = format!("{:?}", JsonWebKey::from(key.clone()));
+    assert!(!debug.contains("private-key"));
+    assert!(debug.contains("[REDACTED]"));
+    assert_eq!(key.d(), "private-key");
+}
+
+#[test]
+pub fn into_parts() {
+    let key = PrivateJwk::new(ec_key(), "private-key".into());
+    let (public, d) = key.into_parts();
+    assert_eq!(public, ec_key());
+    assert_eq!(d.as_str(), "private-key");
+}
+
+#[test]
+pub fn private_key_equality() {
+    let key = PrivateJwk::new(ec_key(), "private-key".into());
+    assert_eq!(key, PrivateJwk::new(ec_key(), "private-key".into()));
+    assert_ne!(key, PrivateJwk::new(ec_key(), "other-private-key".into()));
+}
+
+fn ec_key() -> PublicJwk {
+    PublicJwk {
+        kty: "EC".to_string(),
+        crv: "P-256".to_string(),
+        x: "0000".into(),
+        y: Some("0001".into()),
+        use_: "enc".into(),
+        kid: "".into(),
+    }
+}
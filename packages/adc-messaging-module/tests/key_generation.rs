@@ -0,0 +1,71 @@
+use didcomm_module::{
+    generate_key, pack_anoncrypt, unpack, DirectMessageBuilder, InMemoryDidResolver,
+    InMemorySecretsResolver, KeyCurve, PrivateJwk, PublicJwk,
+};
+use rand::{rngs::StdRng, SeedableRng};
+use serde_json::json;
+
+#[test]
+pub fn generate_keys() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for (curve, kty, crv, use_, has_y) in [
+        (KeyCurve::P256, "EC", "P-256", "enc", true),
+        (KeyCurve::X25519, "OKP", "X25519", "enc", false),
+        (KeyCurve::Ed25519, "OKP", "Ed25519", "sig", false),
+        (KeyCurve::Secp256k1, "EC", "secp256k1", "sig", true),
+    ] {
+        let key = generate_key(curve, &mut rng).unwrap();
+        assert_eq!(key.kty, kty);
+        assert_eq!(key.crv, crv);
+        assert_eq!(key.use_, use_);
+        assert_eq!(key.y.is_some(), has_y);
+        assert!(!key.d().is_empty());
+        assert_eq!(key.kid, key.thumbprint().unwrap());
+    }
+}
+
+#[test]
+pub fn generate_key_from_rng() {
+    let key1 = generate_key(KeyCurve::X25519, &mut StdRng::seed_from_u64(1)).unwrap();
+    let key2 = generate_key(KeyCurve::X25519, &mut StdRng::seed_from_u64(1)).unwrap();
+    let key3 = generate_key(KeyCurve::X25519, &mut StdRng::seed_from_u64(2)).unwrap();
+    assert_eq!(key1, key2);
+    assert_ne!(key1, key3);
+    assert_ne!(key1.kid, key3.kid);
+}
+
+#[test]
+pub fn generated_key_decrypts() {
+    let key = generate_key(KeyCurve::X25519, &mut StdRng::seed_from_u64(0)).unwrap();
+    let kid = "did:example:carol#key-1";
+    let did_resolver = InMemoryDidResolver::from_json(
+        &json!({
+            "id": "did:example:carol",
+            "keyAgreement": [kid],
+            "verificationMethod": [{
+                "id": kid,
+                "type": "JsonWebKey2020",
+                "controller": "did:example:carol",
+                "publicKeyJwk": key.public_key()
+            }]
+        })
+        .to_string(),
+    )
+    .unwrap();
+    let public = PublicJwk {
+        kid: kid.into(),
+        ..key.public_key().clone()
+    };
+    let secrets_resolver =
+        InMemorySecretsResolver::new().add_key(PrivateJwk::new(public, key.d().into()));
+
+    let msg = DirectMessageBuilder::new()
+        .to("did:example:carol".to_string())
+        .message("Hello Carol.".to_string())
+        .build()
+        .unwrap();
+    let packed = pollster::block_on(pack_anoncrypt(&msg, kid, &did_resolver)).unwrap();
+    let unpacked =
+        pollster::block_on(unpack(&packed.message, &did_resolver, &secrets_resolver)).unwrap();
+    assert_eq!(unpacked.message, msg);
+}
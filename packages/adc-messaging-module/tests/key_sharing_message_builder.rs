@@ -1,4 +1,4 @@
-use didcomm_module::{JsonWebKey, KeySharingMessageBuilder, MessageBuilderError};
+use didcomm_module::{KeySharingMessageBuilder, MessageBuilderError, PrivateJwk, PublicJwk};
 use serde_json::json;
 
 #[test]
@@ -15,21 +15,21 @@ pub fn key_sharing() {
         "keys": [
         {
             "crv": "P-256",
-            "d": "0002",
+            "d": "7TCIdt1rhThFtWcEiLnk_COEjh1ZfQhM4bW2wz-dp4A",
             "kid": "<key fingerprint>",
             "kty": "EC",
             "use": "enc",
-            "x": "0000",
-            "y": "0001"
+            "x": "2syLh57B-dGpa0F8p1JrO6JU7UUSF6j7qL-vfk1eOoY",
+            "y": "BgsGtI7UPsObMRjdElxLOrgAO9JggNMjOcfzEPox18w"
         },
         {
             "crv": "P-256",
-            "d": "0004",
+            "d": "sB0bYtpaXyp-h17dDpMx91N3Du1AdN4z1FUq02GbmLw",
             "kid": "<key fingerprint>",
             "kty": "EC",
             "use": "enc",
-            "x": "0002",
-            "y": "0003"
+            "x": "L0crjMN1g0Ih4sYAJ_nGoHUck2cloltUpUVQDhF2nHE",
+            "y": "SxYgE7CmEJYi7IDhgK5jI4ZiajO8jPRZDldVhqFpYoo"
         },
         ]
     },
@@ -40,6 +40,159 @@ pub fn key_sharing() {
     assert_eq!(expected, serde_json::to_value(msg).unwrap());
 }
 
+#[test]
+pub fn key_sharing_public_okp_key() {
+    let msg = KeySharingMessageBuilder::new()
+        .id("message-id".to_string())
+        .add_key(okp_key())
+        .build()
+        .unwrap();
+
+    let expected = json!([{
+        "crv": "Ed25519",
+        "kid": OKP_KEY_THUMBPRINT,
+        "kty": "OKP",
+        "use": "sig",
+        "x": "11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo"
+    }]);
+    assert_eq!(expected, msg.body["keys"]);
+}
+
+#[test]
+pub fn key_sharing_public_part_of_private_key() {
+    let msg = KeySharingMessageBuilder::new()
+        .id("message-id".to_string())
+        .add_key(PublicJwk::from(key1()))
+        .build()
+        .unwrap();
+
+    assert!(msg.body["keys"][0].get("d").is_none());
+}
+
+#[test]
+pub fn key_sharing_thumbprint_kids() {
+    let key = PublicJwk {
+        kid: "".into(),
+        ..okp_key()
+    };
+    let msg = KeySharingMessageBuilder::new()
+        .id("message-id".to_string())
+        .add_key(key)
+        .add_key(okp_key())
+        .thumbprint_kids(true)
+        .build()
+        .unwrap();
+
+    assert_eq!(msg.body["keys"][0]["kid"], OKP_KEY_THUMBPRINT);
+    assert_eq!(msg.body["keys"][1]["kid"], OKP_KEY_THUMBPRINT);
+}
+
+#[test]
+pub fn key_sharing_thumbprint_kid_mismatch() {
+    let msg = KeySharingMessageBuilder::new()
+        .id("message-id".to_string())
+        .add_key(key1())
+        .thumbprint_kids(true)
+        .build();
+    assert!(matches!(
+        msg.unwrap_err(),
+        MessageBuilderError::KidMismatch(kid) if kid == "<key fingerprint>"
+    ));
+}
+
+#[test]
+pub fn key_sharing_invalid_keys() {
+    let cases = [
+        (
+            PublicJwk {
+                kty: "RSA".into(),
+                ..okp_key()
+            },
+            MessageBuilderError::UnsupportedKeyType(OKP_KEY_THUMBPRINT.into(), "RSA".into()),
+        ),
+        (
+            PublicJwk {
+                crv: "P-256".into(),
+                ..okp_key()
+            },
+            MessageBuilderError::UnsupportedCurve(OKP_KEY_THUMBPRINT.into(), "P-256".into()),
+        ),
+        (
+            PublicJwk {
+                x: "not base64url!".into(),
+                ..okp_key()
+            },
+            MessageBuilderError::InvalidKeyEncoding(OKP_KEY_THUMBPRINT.into(), "x"),
+        ),
+        (
+            PublicJwk {
+                x: "0000".into(),
+                ..okp_key()
+            },
+            MessageBuilderError::InvalidKeyLength(OKP_KEY_THUMBPRINT.into(), "x"),
+        ),
+        (
+            PublicJwk {
+                y: Some("11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo".into()),
+                ..okp_key()
+            },
+            MessageBuilderError::UnexpectedKeyField(OKP_KEY_THUMBPRINT.into(), "y"),
+        ),
+        (
+            PublicJwk {
+                y: None,
+                ..key1().public_key().clone()
+            },
+            MessageBuilderError::MissingKeyField("<key fingerprint>".into(), "y"),
+        ),
+        (
+            PublicJwk {
+                // x-coordinate of the second key
+                x: "L0crjMN1g0Ih4sYAJ_nGoHUck2cloltUpUVQDhF2nHE".into(),
+                ..key1().public_key().clone()
+            },
+            MessageBuilderError::InvalidKeyPoint("<key fingerprint>".into()),
+        ),
+        (
+            PublicJwk {
+                use_: "wrap".into(),
+                ..okp_key()
+            },
+            MessageBuilderError::InvalidKeyUse(OKP_KEY_THUMBPRINT.into(), "wrap".into()),
+        ),
+    ];
+
+    for (key, expected) in cases {
+        let msg = KeySharingMessageBuilder::new()
+            .id("message-id".to_string())
+            .add_key(key)
+            .build();
+        assert_eq!(msg.unwrap_err().to_string(), expected.to_string());
+    }
+}
+
+#[test]
+pub fn key_sharing_private_key_mismatch() {
+    let key = PrivateJwk::new(key1().public_key().clone(), key2().d().into());
+    let msg = KeySharingMessageBuilder::new()
+        .id("message-id".to_string())
+        .add_key(key)
+        .build();
+    assert!(matches!(
+        msg.unwrap_err(),
+        MessageBuilderError::PrivateKeyMismatch(_)
+    ));
+}
+
+#[test]
+pub fn key_sharing_builder_debug_redacts_private_keys() {
+    let builder = KeySharingMessageBuilder::new().add_key(key1());
+
+    let debug = format!("{:?}", builder);
+    assert!(debug.contains("<key fingerprint>"));
+    assert!(!debug.contains(key1().d()));
+}
+
 #[test]
 pub fn key_sharing_no_latest_key() {
     let msg = KeySharingMessageBuilder::new()
@@ -57,26 +210,40 @@ pub fn key_sharing_no_previous_keys() {
     assert!(msg.is_ok());
 }
 
-fn key1() -> JsonWebKey {
-    JsonWebKey {
+fn key1() -> PrivateJwk {
+    let public = PublicJwk {
         kty: "EC".to_string(),
         crv: "P-256".to_string(),
-        x: "0000".into(),
-        y: "0001".into(),
-        d: "0002".into(),
+        x: "2syLh57B-dGpa0F8p1JrO6JU7UUSF6j7qL-vfk1eOoY".into(),
+        y: Some("BgsGtI7UPsObMRjdElxLOrgAO9JggNMjOcfzEPox18w".into()),
         use_: "enc".into(),
         kid: "<key fingerprint>".into(),
-    }
+    };
+    PrivateJwk::new(public, "7TCIdt1rhThFtWcEiLnk_COEjh1ZfQhM4bW2wz-dp4A".into())
 }
 
-fn key2() -> JsonWebKey {
-    JsonWebKey {
+fn key2() -> PrivateJwk {
+    let public = PublicJwk {
         kty: "EC".to_string(),
         crv: "P-256".to_string(),
-        x: "0002".into(),
-        y: "0003".into(),
-        d: "0004".into(),
+        x: "L0crjMN1g0Ih4sYAJ_nGoHUck2cloltUpUVQDhF2nHE".into(),
+        y: Some("SxYgE7CmEJYi7IDhgK5jI4ZiajO8jPRZDldVhqFpYoo".into()),
         use_: "enc".into(),
         kid: "<key fingerprint>".into(),
+    };
+    PrivateJwk::new(public, "sB0bYtpaXyp-h17dDpMx91N3Du1AdN4z1FUq02GbmLw".into())
+}
+
+// RFC 8037, appendix A.3
+const OKP_KEY_THUMBPRINT: &str = "kPrK_qmxVWaYVA9wwBF6Iuo3vVzz7TxHCTwXBygrS4k";
+
+fn okp_key() -> PublicJwk {
+    PublicJwk {
+        kty: "OKP".to_string(),
+        crv: "Ed25519".to_string(),
+        x: "11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo".into(),
+        y: None,
+        use_: "sig".into(),
+        kid: OKP_KEY_THUMBPRINT.into(),
     }
 }
@@ -1,5 +1,6 @@
 use didcomm_module::{
-    MediaItemInlined, MediaItemReferenced, MediaItemsMessageBuilder, MessageBuilderError,
+    Ciphering, CipheringError, CipheringParameters, MediaItemInlined, MediaItemReferenced,
+    MediaItemsMessageBuilder, MessageBuilderError, SeededIdGenerator,
 };
 use serde_json::json;
 
@@ -12,9 +13,13 @@ pub fn media_item() {
         description: Some("An example image".to_string()),
         link: "https://example.com/image.png".to_string(),
         hash: "hash-example".to_string(),
-        ciphering: Some(json!({
-            "alg": "aes-256-cbc",
+        ciphering: Some(Ciphering::Aes256Gcm(CipheringParameters {
+            iv: "2f3849399c60cb04b923bd33".to_string(),
+            key: "233f8ce4ac6aa125927ccd98af5750d08c9c61d98a3f5d43cbf096b4caaebe80".to_string(),
         })),
+        format: None,
+        lastmod_time: Some(1_704_067_200),
+        byte_count: Some(2048),
     };
 
     let media_item_inlined = MediaItemInlined {
@@ -22,25 +27,31 @@ pub fn media_item() {
         media_type: "image/jpg".to_string(),
         filename: Some("example.jpg".to_string()),
         description: Some("An example jpg image".to_string()),
-        base64: "base64-example".to_string(),
+        base64: "/9j/4AAQSkZJRg==".to_string(),
+        format: Some("https://example.com/jpg-profile".to_string()),
+        lastmod_time: None,
+        byte_count: None,
     };
 
     let msg = MediaItemsMessageBuilder::new()
         .media_item_referenced(media_item.clone())
         .media_item_inlined(media_item_inlined)
         .id("message-id".to_string())
+        .id_generator(SeededIdGenerator::new(42))
         .build()
         .unwrap();
     let expected = json!({
         "attachments": [
             {
                 "data": {
-                    "base64": "base64-example",
+                    "base64": "/9j/4AAQSkZJRg==",
                 },
                 "description": "An example jpg image",
                 "filename": "example.jpg",
                 "id": "234",
-                "media_type": "image/jpg"
+                "media_type": "image/jpg",
+                "format": "https://example.com/jpg-profile",
+                "byte_count": 10
             },
             {
                 "data": {
@@ -52,20 +63,26 @@ pub fn media_item() {
                 "description": "An example image",
                 "filename": "example.png",
                 "id": "12345",
-                "media_type": "image/png"
+                "media_type": "image/png",
+                "lastmod_time": 1_704_067_200,
+                "byte_count": 2048
             },
         ],
         "body": {
             "items": [
                 {
-                    "@id": msg.body["items"][0]["@id"],
+                    "@id": "bdd73226-2feb-4e95-a8ef-e333b266f103",
                     "attachment_id": "234",
                 },
                 {
-                    "@id": msg.body["items"][1]["@id"],
+                    "@id": "47526757-130f-4f52-981c-e1ff0e4ae394",
                     "attachment_id": "12345",
                     "ciphering": {
-                        "alg": "aes-256-cbc"
+                        "algorithm": "AES-GCM",
+                        "parameters": {
+                            "iv": "2f3849399c60cb04b923bd33",
+                            "key": "233f8ce4ac6aa125927ccd98af5750d08c9c61d98a3f5d43cbf096b4caaebe80"
+                        }
                     },
                 },
 
@@ -87,9 +104,11 @@ pub fn media_item_no_id() {
         description: Some("An example image".to_string()),
         link: "https://example.com/image.png".to_string(),
         hash: "hash-example".to_string(),
-        ciphering: Some(json!({
-            "alg": "aes-256-cbc",
+        ciphering: Some(Ciphering::Aes256Gcm(CipheringParameters {
+            iv: "2f3849399c60cb04b923bd33".to_string(),
+            key: "233f8ce4ac6aa125927ccd98af5750d08c9c61d98a3f5d43cbf096b4caaebe80".to_string(),
         })),
+        ..Default::default()
     };
 
     let msg = MediaItemsMessageBuilder::new()
@@ -98,6 +117,28 @@ pub fn media_item_no_id() {
     assert_eq!(msg.unwrap().id.len(), 36);
 }
 
+#[test]
+pub fn media_item_generated_ids() {
+    let media_item = MediaItemReferenced {
+        id: "12345".to_string(),
+        media_type: "image/png".to_string(),
+        link: "https://example.com/image.png".to_string(),
+        hash: "hash-example".to_string(),
+        ..Default::default()
+    };
+
+    let msg = MediaItemsMessageBuilder::new()
+        .media_item_referenced(media_item)
+        .id_generator(SeededIdGenerator::new(42))
+        .build()
+        .unwrap();
+    assert_eq!(msg.id, "bdd73226-2feb-4e95-a8ef-e333b266f103");
+    assert_eq!(
+        msg.body["items"][0]["@id"],
+        "47526757-130f-4f52-981c-e1ff0e4ae394"
+    );
+}
+
 #[test]
 pub fn media_item_no_media_item() {
     let msg = MediaItemsMessageBuilder::new()
@@ -108,3 +149,65 @@ pub fn media_item_no_media_item() {
         MessageBuilderError::MissingMediaItem
     ));
 }
+
+#[test]
+pub fn media_item_inlined_byte_count() {
+    for base64 in ["aGVsbG8gd29ybGQ=", "aGVsbG8gd29ybGQ", "_-8", "/+8="] {
+        let media_item_inlined = MediaItemInlined {
+            id: "234".to_string(),
+            media_type: "text/plain".to_string(),
+            base64: base64.to_string(),
+            // Replaced by the size of the content.
+            byte_count: Some(1),
+            ..Default::default()
+        };
+
+        let msg = MediaItemsMessageBuilder::new()
+            .media_item_inlined(media_item_inlined)
+            .build()
+            .unwrap();
+        let byte_count = msg.attachments.unwrap()[0].byte_count;
+        assert_eq!(byte_count, Some(if base64.len() > 4 { 11 } else { 2 }));
+    }
+}
+
+#[test]
+pub fn media_item_inlined_invalid_base64() {
+    let media_item_inlined = MediaItemInlined {
+        id: "234".to_string(),
+        media_type: "text/plain".to_string(),
+        base64: "not base64!".to_string(),
+        ..Default::default()
+    };
+
+    let msg = MediaItemsMessageBuilder::new()
+        .media_item_inlined(media_item_inlined)
+        .build();
+    assert!(matches!(
+        msg.unwrap_err(),
+        MessageBuilderError::InvalidBase64(id) if id == "234"
+    ));
+}
+
+#[test]
+pub fn media_item_invalid_ciphering() {
+    let media_item = MediaItemReferenced {
+        id: "12345".to_string(),
+        media_type: "image/png".to_string(),
+        link: "https://example.com/image.png".to_string(),
+        hash: "hash-example".to_string(),
+        ciphering: Some(Ciphering::Aes256Gcm(CipheringParameters {
+            iv: "test-iv".to_string(),
+            key: "test-key".to_string(),
+        })),
+        ..Default::default()
+    };
+
+    let msg = MediaItemsMessageBuilder::new()
+        .media_item_referenced(media_item)
+        .build();
+    assert!(matches!(
+        msg.unwrap_err(),
+        MessageBuilderError::InvalidCiphering(id, CipheringError::InvalidParameter("iv")) if id == "12345"
+    ));
+}
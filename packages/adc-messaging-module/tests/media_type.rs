@@ -0,0 +1,150 @@
+use didcomm_module::{
+    MediaItemInlined, MediaItemReferenced, MediaItemsMessageBuilder, MessageBuilderError,
+};
+
+const PNG: &str = "iVBORw0KGgoAAAANSUhEUg==";
+const PDF: &str = "JVBERi0xLjcK";
+const ZIP: &str = "UEsDBBQABgA=";
+const JPEG: &str = "/9j/4AAQSkZJRg==";
+const MP4: &str = "AAAAGGZ0eXBtcDQy";
+const EXE: &str = "TVqQAA==";
+
+fn inlined(media_type: &str, base64: &str) -> MediaItemInlined {
+    MediaItemInlined {
+        id: "file".to_string(),
+        media_type: media_type.to_string(),
+        base64: base64.to_string(),
+        ..Default::default()
+    }
+}
+
+fn build_inlined(media_type: &str, base64: &str) -> Result<String, MessageBuilderError> {
+    let msg = MediaItemsMessageBuilder::new()
+        .media_item_inlined(inlined(media_type, base64))
+        .build()?;
+    Ok(msg.attachments.unwrap()[0].media_type.clone().unwrap())
+}
+
+fn build_referenced(media_type: &str) -> Result<(), MessageBuilderError> {
+    MediaItemsMessageBuilder::new()
+        .media_item_referenced(MediaItemReferenced {
+            id: "file".to_string(),
+            media_type: media_type.to_string(),
+            link: "https://example.com/file".to_string(),
+            hash: "hash-example".to_string(),
+            ..Default::default()
+        })
+        .build()
+        .map(|_| ())
+}
+
+#[test]
+pub fn valid_media_types() {
+    for media_type in [
+        "image/png",
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "image/svg+xml",
+        "text/plain; charset=utf-8",
+        "text/plain;charset=\"utf-8\"",
+        "multipart/form-data; boundary=abc; charset=us-ascii",
+    ] {
+        assert!(build_referenced(media_type).is_ok(), "{}", media_type);
+    }
+}
+
+#[test]
+pub fn invalid_media_types() {
+    for media_type in [
+        "",
+        "pdf",
+        "image/",
+        "/png",
+        "image/png/large",
+        "image png",
+        "image/png;",
+        "text/plain; charset",
+        "text/plain; charset=utf 8",
+        "text/plain; charset=\"utf-8",
+    ] {
+        let result = build_referenced(media_type);
+        assert!(
+            matches!(result, Err(MessageBuilderError::InvalidMediaType(ref id, ref m)) if id == "file" && m == media_type),
+            "{}",
+            media_type
+        );
+    }
+}
+
+#[test]
+pub fn invalid_inlined_media_type() {
+    let result = build_inlined("pdf", PDF);
+    assert!(
+        matches!(result, Err(MessageBuilderError::InvalidMediaType(id, m)) if id == "file" && m == "pdf")
+    );
+}
+
+#[test]
+pub fn fills_in_sniffed_media_type() {
+    assert_eq!(build_inlined("", PNG).unwrap(), "image/png");
+    assert_eq!(build_inlined("", PDF).unwrap(), "application/pdf");
+    assert_eq!(build_inlined("", JPEG).unwrap(), "image/jpeg");
+    assert_eq!(
+        build_inlined("", "aGVsbG8gd29ybGQ=").unwrap(),
+        "application/octet-stream"
+    );
+}
+
+#[test]
+pub fn accepts_matching_media_type() {
+    assert_eq!(build_inlined("image/png", PNG).unwrap(), "image/png");
+    assert_eq!(build_inlined("IMAGE/JPG", JPEG).unwrap(), "IMAGE/JPG");
+    assert!(build_inlined("application/octet-stream", PNG).is_ok());
+    assert!(build_inlined("text/plain", "aGVsbG8gd29ybGQ=").is_ok());
+    assert!(build_inlined("application/octet-stream", EXE).is_ok());
+}
+
+#[test]
+pub fn accepts_mp4_based_formats() {
+    for media_type in ["video/mp4", "video/quicktime", "audio/mp4", "image/heic"] {
+        assert!(build_inlined(media_type, MP4).is_ok(), "{}", media_type);
+    }
+}
+
+#[test]
+pub fn accepts_zip_based_formats() {
+    for media_type in [
+        "application/zip",
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "application/epub+zip",
+        "application/java-archive",
+    ] {
+        assert!(build_inlined(media_type, ZIP).is_ok(), "{}", media_type);
+    }
+}
+
+#[test]
+pub fn rejects_disguised_content() {
+    for (media_type, base64, sniffed) in [
+        ("image/png", PDF, "application/pdf"),
+        ("application/pdf", ZIP, "application/zip"),
+        ("image/jpeg", PNG, "image/png"),
+        ("text/plain", PNG, "image/png"),
+        ("application/vnd.ms-excel", ZIP, "application/zip"),
+        ("application/x-evil+zip", ZIP, "application/zip"),
+        ("video/x-msvideo", MP4, "video/mp4"),
+        ("image/png", EXE, "application/octet-stream"),
+        ("application/zip", EXE, "application/octet-stream"),
+        ("video/mp4", "aGVsbG8gd29ybGQ=", "application/octet-stream"),
+    ] {
+        let result = build_inlined(media_type, base64);
+        assert!(
+            matches!(
+                result,
+                Err(MessageBuilderError::MediaTypeMismatch(ref id, ref declared, ref s))
+                    if id == "file" && declared == media_type && s == sniffed
+            ),
+            "{}",
+            media_type
+        );
+    }
+}
@@ -0,0 +1,242 @@
+use didcomm_module::{
+    didcomm::Message, parse_message, Ciphering, CipheringError, CipheringParameters,
+    DirectMessageBuilder, JsonWebKey, KeySharingMessageBuilder, MediaItem, MediaItemInlined,
+    MediaItemReferenced, MediaItemsMessageBuilder, MessageParserError, ParsedMessage, PrivateJwk,
+    PublicJwk,
+};
+use serde_json::json;
+
+#[test]
+pub fn parse_direct_message() {
+    let msg = DirectMessageBuilder::new()
+        .id("message-id".to_string())
+        .from("did:example:alice".to_string())
+        .to("did:example:bob".to_string())
+        .created_time(Some(1234))
+        .thid("thread-id".to_string())
+        .lang("en".to_string())
+        .message("Hello World.".to_string())
+        .build()
+        .unwrap();
+
+    let ParsedMessage::Direct(parsed) = parse_message(msg).unwrap() else {
+        panic!("expected a direct message");
+    };
+    assert_eq!(parsed.id, "message-id");
+    assert_eq!(parsed.from.as_deref(), Some("did:example:alice"));
+    assert_eq!(parsed.to, Some(vec!["did:example:bob".to_string()]));
+    assert_eq!(parsed.created_time, Some(1234));
+    assert_eq!(parsed.thid.as_deref(), Some("thread-id"));
+    assert_eq!(parsed.pthid, None);
+    assert_eq!(parsed.message, "Hello World.");
+    assert_eq!(parsed.lang.as_deref(), Some("en"));
+}
+
+#[test]
+pub fn parse_direct_message_invalid_lang() {
+    let mut msg = DirectMessageBuilder::new()
+        .message("Hello World.".to_string())
+        .build()
+        .unwrap();
+    msg.extra_headers.insert("lang".into(), json!(42));
+
+    assert!(matches!(
+        parse_message(msg).unwrap_err(),
+        MessageParserError::InvalidHeader("lang")
+    ));
+}
+
+#[test]
+pub fn parse_key_sharing_message() {
+    let public_key = PublicJwk {
+        kty: "OKP".to_string(),
+        crv: "X25519".to_string(),
+        x: "avH0O2Y4tqLAq8y9zpianr8ajii5m4F_mICrzNlatXs".into(),
+        y: None,
+        use_: "enc".into(),
+        kid: "key-id".into(),
+    };
+    let private_key = PrivateJwk::new(
+        PublicJwk {
+            kid: "key-id-2".into(),
+            ..public_key.clone()
+        },
+        "r-jK2cO3taR8LQnJB1_ikLBTAnOtShJOsHXRUWT-aZA".into(),
+    );
+    let msg = KeySharingMessageBuilder::new()
+        .id("message-id".to_string())
+        .add_key(public_key.clone())
+        .add_key(private_key.clone())
+        .build()
+        .unwrap();
+
+    let ParsedMessage::KeySharing(parsed) = parse_message(msg).unwrap() else {
+        panic!("expected a key sharing message");
+    };
+    assert_eq!(
+        parsed.keys,
+        vec![
+            JsonWebKey::Public(public_key),
+            JsonWebKey::Private(private_key)
+        ]
+    );
+}
+
+#[test]
+pub fn parse_media_items_message() {
+    let media_item_referenced = MediaItemReferenced {
+        id: "12345".to_string(),
+        media_type: "image/png".to_string(),
+        filename: Some("example.png".to_string()),
+        description: Some("An example image".to_string()),
+        link: "https://example.com/image.png".to_string(),
+        hash: "hash-example".to_string(),
+        ciphering: Some(Ciphering::Aes256Gcm(CipheringParameters {
+            iv: "2f3849399c60cb04b923bd33".to_string(),
+            key: "233f8ce4ac6aa125927ccd98af5750d08c9c61d98a3f5d43cbf096b4caaebe80".to_string(),
+        })),
+        format: Some("https://example.com/png-profile".to_string()),
+        lastmod_time: Some(1_704_067_200),
+        byte_count: Some(2048),
+    };
+    let media_item_inlined = MediaItemInlined {
+        id: "234".to_string(),
+        media_type: "image/jpg".to_string(),
+        filename: None,
+        description: None,
+        base64: "/9j/4AAQSkZJRg==".to_string(),
+        format: None,
+        lastmod_time: Some(1_704_067_200),
+        byte_count: Some(10),
+    };
+
+    let msg = MediaItemsMessageBuilder::new()
+        .id("message-id".to_string())
+        .media_item_referenced(media_item_referenced.clone())
+        .media_item_inlined(media_item_inlined.clone())
+        .build()
+        .unwrap();
+
+    let ParsedMessage::MediaItems(parsed) = parse_message(msg).unwrap() else {
+        panic!("expected a media items message");
+    };
+    assert_eq!(
+        parsed.media_items,
+        vec![
+            MediaItem::Inlined(media_item_inlined),
+            MediaItem::Referenced(media_item_referenced),
+        ]
+    );
+}
+
+#[test]
+pub fn parse_unknown_type() {
+    let msg = Message::build(
+        "message-id".to_string(),
+        "https://didcomm.org/trust-ping/2.0/ping".to_string(),
+        json!({}),
+    )
+    .finalize();
+
+    assert!(matches!(
+        parse_message(msg).unwrap_err(),
+        MessageParserError::UnknownMessageType(type_) if type_ == "https://didcomm.org/trust-ping/2.0/ping"
+    ));
+}
+
+#[test]
+pub fn parse_missing_body_field() {
+    let msg = Message::build(
+        "message-id".to_string(),
+        didcomm_module::DIRECT_MESSAGE_TYPE.to_string(),
+        json!({}),
+    )
+    .finalize();
+
+    assert!(matches!(
+        parse_message(msg).unwrap_err(),
+        MessageParserError::MissingBodyField("content")
+    ));
+}
+
+#[test]
+pub fn parse_dangling_attachment_id() {
+    let msg = Message::build(
+        "message-id".to_string(),
+        didcomm_module::MEDIA_ITEMS_MESSAGE_TYPE.to_string(),
+        json!({
+            "items": [
+                {
+                    "@id": "item-id",
+                    "attachment_id": "missing",
+                }
+            ]
+        }),
+    )
+    .finalize();
+
+    assert!(matches!(
+        parse_message(msg).unwrap_err(),
+        MessageParserError::DanglingAttachmentId(id) if id == "missing"
+    ));
+}
+
+fn media_items_message_with_ciphering(ciphering: serde_json::Value) -> Message {
+    serde_json::from_value(json!({
+        "id": "message-id",
+        "typ": "application/didcomm-plain+json",
+        "type": didcomm_module::MEDIA_ITEMS_MESSAGE_TYPE,
+        "body": {
+            "items": [
+                {
+                    "@id": "item-id",
+                    "attachment_id": "12345",
+                    "ciphering": ciphering
+                }
+            ]
+        },
+        "attachments": [
+            {
+                "id": "12345",
+                "media_type": "image/png",
+                "data": {
+                    "links": ["https://example.com/image.png"],
+                    "hash": "hash-example"
+                }
+            }
+        ]
+    }))
+    .unwrap()
+}
+
+#[test]
+pub fn parse_unknown_ciphering() {
+    let ciphering = json!({ "alg": "aes-256-cbc" });
+    let msg = media_items_message_with_ciphering(ciphering.clone());
+
+    let ParsedMessage::MediaItems(parsed) = parse_message(msg).unwrap() else {
+        panic!("expected a media items message");
+    };
+    let MediaItem::Referenced(media_item) = &parsed.media_items[0] else {
+        panic!("expected a referenced media item");
+    };
+    assert_eq!(media_item.ciphering, Some(Ciphering::Unknown(ciphering)));
+}
+
+#[test]
+pub fn parse_invalid_ciphering() {
+    for (ciphering, parameter) in [
+        (
+            json!({ "algorithm": "AES-GCM", "parameters": { "iv": "test-iv", "key": "test-key" } }),
+            "iv",
+        ),
+        (json!({ "algorithm": "XChaCha20-Poly1305" }), "parameters"),
+    ] {
+        let msg = media_items_message_with_ciphering(ciphering);
+        assert!(matches!(
+            parse_message(msg).unwrap_err(),
+            MessageParserError::InvalidCiphering(id, CipheringError::InvalidParameter(p))
+                if id == "12345" && p == parameter
+        ));
+    }
+}
@@ -1,3 +1,21 @@
+// Test files are also built as separate targets, so each includes the shared fixtures in `common`.
+#![allow(clippy::duplicate_mod)]
+
+mod ciphering;
+mod did_resolver;
+mod did_validation;
 mod direct_message_builder;
+mod error;
+mod freshness;
+mod id_generator;
+mod key;
+mod key_generation;
 mod key_sharing_message_builder;
 mod media_item_message_builder;
+mod media_type;
+mod message_parser;
+mod multihash;
+mod pack;
+mod recipients;
+mod secrets_resolver;
+mod unpack;
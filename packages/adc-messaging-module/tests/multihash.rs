@@ -0,0 +1,83 @@
+use didcomm_module::{
+    multihash, verify_multihash, HashAlgorithm, MediaItemReferenced, MultihashHasher,
+};
+
+const CONTENT: &[u8] = b"hello world";
+
+#[test]
+pub fn multihash_algorithms() {
+    assert_eq!(
+        multihash(HashAlgorithm::Sha2_256, CONTENT),
+        "zQmaozNR7DZHQK1ZcU9p7QdrshMvXqWK6gpu5rmrkPdT3L4"
+    );
+    assert_eq!(
+        multihash(HashAlgorithm::Blake2b256, CONTENT),
+        "z2Drjgb6mymtFmrnLpYFVYj3KnZ1KMTGv1EKXNnkNM9xbVrUaCf"
+    );
+    assert_eq!(
+        multihash(HashAlgorithm::Sha3_256, CONTENT),
+        "zW1gCW3ANJ7a4QiYHYmjwraZsgMXmK1QqHJRzjpe832gK5Z"
+    );
+}
+
+#[test]
+pub fn incremental_hasher() {
+    let mut hasher = MultihashHasher::new(HashAlgorithm::Blake2b256);
+    hasher.update(b"hello ");
+    hasher.update(b"world");
+    assert_eq!(
+        hasher.finalize(),
+        multihash(HashAlgorithm::Blake2b256, CONTENT)
+    );
+}
+
+#[test]
+pub fn verify_hash() {
+    for algorithm in [
+        HashAlgorithm::Sha2_256,
+        HashAlgorithm::Blake2b256,
+        HashAlgorithm::Sha3_256,
+    ] {
+        let hash = multihash(algorithm, CONTENT);
+        assert!(verify_multihash(&hash, CONTENT));
+        assert!(!verify_multihash(&hash, b"hello world!"));
+    }
+
+    // Hex digests are not multihashes.
+    assert!(!verify_multihash(
+        "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9",
+        CONTENT
+    ));
+    assert!(!verify_multihash("z", CONTENT));
+}
+
+#[test]
+pub fn media_item_from_content() {
+    let media_item = MediaItemReferenced::from_content(
+        "12345".to_string(),
+        "text/plain".to_string(),
+        "https://example.com/hello.txt".to_string(),
+        CONTENT,
+        HashAlgorithm::Sha2_256,
+    );
+    assert_eq!(
+        media_item.hash,
+        "zQmaozNR7DZHQK1ZcU9p7QdrshMvXqWK6gpu5rmrkPdT3L4"
+    );
+    assert!(media_item.verify_hash(CONTENT));
+    assert!(!media_item.verify_hash(b"tampered"));
+}
+
+#[test]
+pub fn media_item_from_reader() {
+    let media_item = MediaItemReferenced::from_reader(
+        "12345".to_string(),
+        "text/plain".to_string(),
+        "https://example.com/hello.txt".to_string(),
+        CONTENT,
+        HashAlgorithm::Sha3_256,
+    )
+    .unwrap();
+    assert_eq!(media_item.hash, multihash(HashAlgorithm::Sha3_256, CONTENT));
+    assert!(media_item.verify_hash(CONTENT));
+}
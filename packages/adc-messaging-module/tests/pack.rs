@@ -0,0 +1,134 @@
+use didcomm_module::{
+    didcomm::{
+        did::resolvers::ExampleDIDResolver,
+        secrets::resolvers::ExampleSecretsResolver,
+        test_vectors::{
+            ALICE_DID, ALICE_DID_DOC, ALICE_SECRET_KEY_AGREEMENT_KEY_P256,
+            ALICE_SECRET_KEY_AGREEMENT_KEY_X25519, BOB_DID, BOB_DID_DOC, BOB_SECRETS,
+        },
+        Message, UnpackOptions,
+    },
+    pack_anoncrypt, pack_authcrypt, EnvelopeError, InMemoryDidResolver, InMemorySecretsResolver,
+};
+
+#[path = "common/mod.rs"]
+mod common;
+
+use common::{key, message, BOB_KID};
+
+#[test]
+pub fn anoncrypt() {
+    let msg = message();
+    let did_resolver = InMemoryDidResolver::from(vec![ALICE_DID_DOC.clone(), BOB_DID_DOC.clone()]);
+
+    let packed = pollster::block_on(pack_anoncrypt(&msg, BOB_DID, &did_resolver)).unwrap();
+    assert!(packed.from_kid.is_none());
+    assert!(!packed.to_kids.is_empty());
+
+    let (unpacked, metadata) = unpack(&packed.message);
+    assert_eq!(unpacked, msg);
+    assert!(metadata.encrypted);
+    assert!(metadata.anonymous_sender);
+}
+
+#[test]
+pub fn anoncrypt_to_kid() {
+    let msg = message();
+    let did_resolver = InMemoryDidResolver::from(vec![ALICE_DID_DOC.clone(), BOB_DID_DOC.clone()]);
+
+    let packed = pollster::block_on(pack_anoncrypt(&msg, BOB_KID, &did_resolver)).unwrap();
+    assert_eq!(packed.to_kids, vec![BOB_KID.to_string()]);
+}
+
+#[test]
+pub fn authcrypt() {
+    let msg = message();
+    let did_resolver = InMemoryDidResolver::from(vec![ALICE_DID_DOC.clone(), BOB_DID_DOC.clone()]);
+    let secrets_resolver =
+        InMemorySecretsResolver::new().add_key(key(&ALICE_SECRET_KEY_AGREEMENT_KEY_P256));
+
+    let packed = pollster::block_on(pack_authcrypt(
+        &msg,
+        BOB_DID,
+        ALICE_DID,
+        &did_resolver,
+        &secrets_resolver,
+    ))
+    .unwrap();
+    assert_eq!(
+        packed.from_kid.as_deref(),
+        Some("did:example:alice#key-p256-1")
+    );
+
+    let (unpacked, metadata) = unpack(&packed.message);
+    assert_eq!(unpacked, msg);
+    assert!(metadata.authenticated);
+    assert!(!metadata.anonymous_sender);
+}
+
+#[test]
+pub fn authcrypt_x25519() {
+    let msg = message();
+    let did_resolver = InMemoryDidResolver::from(vec![ALICE_DID_DOC.clone(), BOB_DID_DOC.clone()]);
+    let secrets_resolver =
+        InMemorySecretsResolver::new().add_key(key(&ALICE_SECRET_KEY_AGREEMENT_KEY_X25519));
+
+    let packed = pollster::block_on(pack_authcrypt(
+        &msg,
+        BOB_DID,
+        ALICE_DID,
+        &did_resolver,
+        &secrets_resolver,
+    ))
+    .unwrap();
+    assert_eq!(
+        packed.from_kid.as_deref(),
+        Some("did:example:alice#key-x25519-1")
+    );
+
+    let (unpacked, _) = unpack(&packed.message);
+    assert_eq!(unpacked, msg);
+}
+
+#[test]
+pub fn authcrypt_missing_secret() {
+    let msg = message();
+    let did_resolver = InMemoryDidResolver::from(vec![ALICE_DID_DOC.clone(), BOB_DID_DOC.clone()]);
+    let secrets_resolver = InMemorySecretsResolver::new();
+
+    let packed = pollster::block_on(pack_authcrypt(
+        &msg,
+        BOB_DID,
+        ALICE_DID,
+        &did_resolver,
+        &secrets_resolver,
+    ));
+    assert!(matches!(
+        packed.unwrap_err(),
+        EnvelopeError::SecretNotFound(_)
+    ));
+}
+
+#[test]
+pub fn anoncrypt_unknown_recipient() {
+    let msg = message();
+    let did_resolver = InMemoryDidResolver::from(vec![ALICE_DID_DOC.clone()]);
+
+    let packed = pollster::block_on(pack_anoncrypt(&msg, BOB_DID, &did_resolver));
+    assert!(matches!(
+        packed.unwrap_err(),
+        EnvelopeError::DidNotResolved(_)
+    ));
+}
+
+fn unpack(packed: &str) -> (Message, didcomm_module::didcomm::UnpackMetadata) {
+    let did_resolver = ExampleDIDResolver::new(vec![ALICE_DID_DOC.clone(), BOB_DID_DOC.clone()]);
+    let secrets_resolver = ExampleSecretsResolver::new(BOB_SECRETS.clone());
+    pollster::block_on(Message::unpack(
+        packed,
+        &did_resolver,
+        &secrets_resolver,
+        &UnpackOptions::default(),
+    ))
+    .unwrap()
+}
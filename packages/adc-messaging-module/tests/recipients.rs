@@ -0,0 +1,62 @@
+use didcomm_module::{split_by_recipient, DirectMessageBuilder};
+
+#[test]
+pub fn deduplicated_recipients() {
+    let msg = DirectMessageBuilder::new()
+        .to("did:example:bob".to_string())
+        .to_many([
+            "did:example:charlie".to_string(),
+            "did:example:bob".to_string(),
+            "did:example:alice".to_string(),
+            "did:example:charlie".to_string(),
+        ])
+        .message("Hello World.".to_string())
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        msg.to,
+        Some(vec![
+            "did:example:bob".to_string(),
+            "did:example:charlie".to_string(),
+            "did:example:alice".to_string(),
+        ])
+    );
+}
+
+#[test]
+pub fn split_message_by_recipient() {
+    let msg = DirectMessageBuilder::new()
+        .id("message-id".to_string())
+        .from("did:example:alice".to_string())
+        .to_many([
+            "did:example:bob".to_string(),
+            "did:example:charlie".to_string(),
+        ])
+        .message("Hello World.".to_string())
+        .build()
+        .unwrap();
+
+    let copies = split_by_recipient(&msg);
+    assert_eq!(copies.len(), 2);
+    assert_eq!(copies[0].to, Some(vec!["did:example:bob".to_string()]));
+    assert_eq!(copies[1].to, Some(vec!["did:example:charlie".to_string()]));
+    for copy in copies {
+        assert_eq!(copy.id, "message-id");
+        assert_eq!(copy.from.as_deref(), Some("did:example:alice"));
+        assert_eq!(copy.body, msg.body);
+    }
+}
+
+#[test]
+pub fn split_message_without_recipients() {
+    let msg = DirectMessageBuilder::new()
+        .message("Hello World.".to_string())
+        .build()
+        .unwrap();
+
+    let copies = split_by_recipient(&msg);
+    assert_eq!(copies.len(), 1);
+    assert_eq!(copies[0].id, msg.id);
+    assert_eq!(copies[0].to, None);
+}
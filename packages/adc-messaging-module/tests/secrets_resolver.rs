@@ -0,0 +1,71 @@
+use didcomm_module::{InMemorySecretsResolver, PublicJwk, ResolverError, SecretsResolver};
+use serde_json::json;
+
+#[path = "common/mod.rs"]
+mod common;
+
+use common::key_with_kid as key;
+
+#[test]
+pub fn get_key() {
+    let resolver = InMemorySecretsResolver::new().add_key(key("key-1"));
+
+    let found = pollster::block_on(resolver.get_key("key-1")).unwrap();
+    assert_eq!(found, Some(key("key-1")));
+    let missing = pollster::block_on(resolver.get_key("key-2")).unwrap();
+    assert_eq!(missing, None);
+}
+
+#[test]
+pub fn find_keys() {
+    let resolver = InMemorySecretsResolver::from(vec![key("key-1"), key("key-3")]);
+
+    let found = pollster::block_on(resolver.find_keys(&["key-1", "key-2", "key-3"])).unwrap();
+    assert_eq!(found, vec!["key-1", "key-3"]);
+}
+
+#[test]
+pub fn remove_key() {
+    let mut resolver = InMemorySecretsResolver::new().add_key(key("key-1"));
+
+    assert!(resolver.remove_key("key-1"));
+    assert!(!resolver.contains_key("key-1"));
+    assert!(!resolver.remove_key("key-1"));
+}
+
+#[test]
+pub fn from_json_key_set() {
+    let json = json!({ "keys": [key("key-1"), key("key-2")] });
+
+    let resolver = InMemorySecretsResolver::from_json(&json.to_string()).unwrap();
+    assert!(resolver.contains_key("key-1"));
+    assert!(resolver.contains_key("key-2"));
+}
+
+#[test]
+pub fn from_json_key_list() {
+    let json = json!([key("key-1")]);
+
+    let resolver = InMemorySecretsResolver::from_json(&json.to_string()).unwrap();
+    assert!(resolver.contains_key("key-1"));
+}
+
+#[test]
+pub fn from_json_public_key() {
+    let json = json!([PublicJwk::from(key("key-1"))]);
+
+    let resolver = InMemorySecretsResolver::from_json(&json.to_string());
+    assert!(matches!(
+        resolver.unwrap_err(),
+        ResolverError::InvalidKey(_)
+    ));
+}
+
+#[test]
+pub fn from_json_invalid_key() {
+    let resolver = InMemorySecretsResolver::from_json(r#"[{"kty": "EC"}]"#);
+    assert!(matches!(
+        resolver.unwrap_err(),
+        ResolverError::InvalidKey(_)
+    ));
+}
@@ -0,0 +1,91 @@
+use didcomm_module::{
+    didcomm::test_vectors::{
+        ALICE_DID, ALICE_DID_DOC, ALICE_SECRET_KEY_AGREEMENT_KEY_P256, BOB_DID_DOC,
+        BOB_SECRET_KEY_AGREEMENT_KEY_P256_1,
+    },
+    pack_anoncrypt, pack_authcrypt, unpack, EnvelopeError, InMemoryDidResolver,
+    InMemorySecretsResolver,
+};
+
+#[path = "common/mod.rs"]
+mod common;
+
+use common::{key, message, BOB_KID};
+
+#[test]
+pub fn unpack_anoncrypt() {
+    let msg = message();
+    let did_resolver = InMemoryDidResolver::from(vec![ALICE_DID_DOC.clone(), BOB_DID_DOC.clone()]);
+    let secrets_resolver =
+        InMemorySecretsResolver::new().add_key(key(&BOB_SECRET_KEY_AGREEMENT_KEY_P256_1));
+    let packed = pollster::block_on(pack_anoncrypt(&msg, BOB_KID, &did_resolver)).unwrap();
+
+    let unpacked =
+        pollster::block_on(unpack(&packed.message, &did_resolver, &secrets_resolver)).unwrap();
+    assert_eq!(unpacked.message, msg);
+    assert!(unpacked.encrypted);
+    assert!(!unpacked.authenticated);
+    assert!(!unpacked.signed);
+    assert_eq!(unpacked.from_kid, None);
+    assert_eq!(unpacked.sender, None);
+    assert_eq!(unpacked.to_kids, vec![BOB_KID.to_string()]);
+}
+
+#[test]
+pub fn unpack_authcrypt() {
+    let msg = message();
+    let did_resolver = InMemoryDidResolver::from(vec![ALICE_DID_DOC.clone(), BOB_DID_DOC.clone()]);
+    let packed = pollster::block_on(pack_authcrypt(
+        &msg,
+        BOB_KID,
+        ALICE_DID,
+        &did_resolver,
+        &InMemorySecretsResolver::new().add_key(key(&ALICE_SECRET_KEY_AGREEMENT_KEY_P256)),
+    ))
+    .unwrap();
+
+    let unpacked = pollster::block_on(unpack(
+        &packed.message,
+        &did_resolver,
+        &InMemorySecretsResolver::new().add_key(key(&BOB_SECRET_KEY_AGREEMENT_KEY_P256_1)),
+    ))
+    .unwrap();
+    assert_eq!(unpacked.message, msg);
+    assert!(unpacked.encrypted);
+    assert!(unpacked.authenticated);
+    assert!(!unpacked.signed);
+    assert_eq!(unpacked.from_kid, packed.from_kid);
+    assert_eq!(unpacked.sender.as_deref(), Some(ALICE_DID));
+}
+
+#[test]
+pub fn unpack_plaintext() {
+    let msg = message();
+
+    let unpacked = pollster::block_on(unpack(
+        &serde_json::to_string(&msg).unwrap(),
+        &InMemoryDidResolver::new(),
+        &InMemorySecretsResolver::new(),
+    ))
+    .unwrap();
+    assert_eq!(unpacked.message, msg);
+    assert!(!unpacked.encrypted);
+    assert!(!unpacked.authenticated);
+}
+
+#[test]
+pub fn unpack_missing_secret() {
+    let msg = message();
+    let did_resolver = InMemoryDidResolver::from(vec![ALICE_DID_DOC.clone(), BOB_DID_DOC.clone()]);
+    let packed = pollster::block_on(pack_anoncrypt(&msg, BOB_KID, &did_resolver)).unwrap();
+
+    let unpacked = pollster::block_on(unpack(
+        &packed.message,
+        &did_resolver,
+        &InMemorySecretsResolver::new(),
+    ));
+    assert!(matches!(
+        unpacked.unwrap_err(),
+        EnvelopeError::SecretNotFound(_)
+    ));
+}
@@ -32,9 +32,9 @@ function main() {
             {
                 kty: "EC",
                 crv: "P-256",
-                x: "0000",
-                y: "0001",
-                d: "0002",
+                x: "2syLh57B-dGpa0F8p1JrO6JU7UUSF6j7qL-vfk1eOoY",
+                y: "BgsGtI7UPsObMRjdElxLOrgAO9JggNMjOcfzEPox18w",
+                d: "7TCIdt1rhThFtWcEiLnk_COEjh1ZfQhM4bW2wz-dp4A",
                 use: "enc",
                 kid: "key-id"
             },
@@ -42,9 +42,9 @@ function main() {
             {
                 kty: "EC",
                 crv: "P-256",
-                x: "0003",
-                y: "0004",
-                d: "0005",
+                x: "L0crjMN1g0Ih4sYAJ_nGoHUck2cloltUpUVQDhF2nHE",
+                y: "SxYgE7CmEJYi7IDhgK5jI4ZiajO8jPRZDldVhqFpYoo",
+                d: "sB0bYtpaXyp-h17dDpMx91N3Du1AdN4z1FUq02GbmLw",
                 use: "enc",
                 kid: "key-id-2"
             }
@@ -65,7 +65,7 @@ function main() {
         mediaItems: [
             {
                 id: "media-item-id-1",
-                media_type: "pdf",
+                media_type: "application/pdf",
                 filename: "test.pdf",
                 description: "document for test",
                 link: "ipfs://example-link/test.pdf",
@@ -73,8 +73,8 @@ function main() {
                 ciphering: {
                     algorithm: "AES-GCM",
                     parameters: {
-                        iv: "test-iv",
-                        key: "test-key"
+                        iv: "2f3849399c60cb04b923bd33",
+                        key: "233f8ce4ac6aa125927ccd98af5750d08c9c61d98a3f5d43cbf096b4caaebe80"
                     }
                 }
             }
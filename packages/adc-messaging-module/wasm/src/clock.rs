@@ -0,0 +1,15 @@
+use didcomm_module::Clock;
+
+/// Clock reading the JavaScript `Date.now()`, since the system time is not available on `wasm32-unknown-unknown`.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct JsClock;
+
+impl Clock for JsClock {
+    fn now(&self) -> u64 {
+        (js_sys::Date::now() / 1000.0) as u64
+    }
+
+    fn now_millis(&self) -> u64 {
+        js_sys::Date::now() as u64
+    }
+}
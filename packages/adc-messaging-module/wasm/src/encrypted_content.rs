@@ -0,0 +1,33 @@
+use crate::types::Ciphering;
+use wasm_bindgen::prelude::*;
+
+/// Content encrypted by `encryptContent`, to be uploaded to the link of a referenced media item.
+#[wasm_bindgen]
+pub struct EncryptedContent {
+    ciphertext: Vec<u8>,
+    ciphering: Ciphering,
+}
+
+#[wasm_bindgen]
+impl EncryptedContent {
+    /// Encrypted content followed by the authentication tag.
+    #[wasm_bindgen(getter)]
+    pub fn ciphertext(&self) -> Vec<u8> {
+        self.ciphertext.clone()
+    }
+
+    /// Encryption information for the `ciphering` of the media item.
+    #[wasm_bindgen(getter)]
+    pub fn ciphering(&self) -> Ciphering {
+        self.ciphering.clone()
+    }
+}
+
+impl From<didcomm_module::EncryptedContent> for EncryptedContent {
+    fn from(val: didcomm_module::EncryptedContent) -> Self {
+        EncryptedContent {
+            ciphertext: val.ciphertext,
+            ciphering: val.ciphering.into(),
+        }
+    }
+}
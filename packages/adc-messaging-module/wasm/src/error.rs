@@ -0,0 +1,37 @@
+use core::fmt::Display;
+use serde::Serialize;
+use tsify::Tsify;
+use wasm_bindgen::JsValue;
+
+/// Code of errors parsing JSON input, e.g. a malformed message.
+pub(crate) const INVALID_JSON: &str = "INVALID_JSON";
+/// Code of errors converting a result to a JavaScript value.
+pub(crate) const RESULT_SERIALIZATION_ERROR: &str = "RESULT_SERIALIZATION_ERROR";
+/// Code of errors adding a key without private key to a `SecretsStore`.
+pub(crate) const MISSING_PRIVATE_KEY: &str = "MISSING_PRIVATE_KEY";
+
+/// Error thrown by the helper functions.
+#[derive(Tsify, Serialize, Debug)]
+pub struct ModuleError {
+    /// Stable, machine-readable code of the error, e.g. "INVALID_DID".
+    pub(crate) code: String,
+    /// Description of the error.
+    pub(crate) message: String,
+}
+
+impl ModuleError {
+    /// Creates an error with the given code, prefixing the message of `error` with `context`,
+    /// e.g. "Failed to build message".
+    pub(crate) fn new(code: &str, context: &str, error: impl Display) -> Self {
+        Self {
+            code: code.to_string(),
+            message: format!("{}: {}", context, error),
+        }
+    }
+}
+
+impl From<ModuleError> for JsValue {
+    fn from(error: ModuleError) -> Self {
+        serde_wasm_bindgen::to_value(&error).unwrap_or_else(|_| JsValue::from(error.message))
+    }
+}
@@ -1,18 +1,41 @@
-use crate::types::{DirectMessageOptions, KeySharingMessageOptions, MediaItemsMessageOptions};
-use didcomm_module::{DirectMessageBuilder, KeySharingMessageBuilder, MediaItemsMessageBuilder};
+use crate::clock::JsClock;
+use crate::encrypted_content::EncryptedContent;
+use crate::error::{INVALID_JSON, ModuleError, RESULT_SERIALIZATION_ERROR};
+use crate::secrets_store::SecretsStore;
+use crate::types::{
+    CipherAlgorithm, Ciphering, DirectMessageOptions, FreshnessOptions, HashAlgorithm, JsonWebKey,
+    KeyCurve, KeySharingMessageOptions, MediaItemsMessageOptions, PackMessageOptions,
+    PackedMessage, ParsedMessage, UnpackMessageOptions, UnpackedMessage,
+};
+use didcomm_module::{
+    Clock, DirectMessageBuilder, InMemoryDidResolver, KeySharingMessageBuilder,
+    MediaItemsMessageBuilder, check_freshness, decrypt_content, didcomm::Message, encrypt_content,
+    generate_key, multihash, pack_anoncrypt, pack_authcrypt, parse_message, split_by_recipient,
+    unpack, verify_multihash,
+};
+use js_sys::Promise;
+use rand_core::OsRng;
 use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::future_to_promise;
 
 /// Helper function for creating a DIDComm message for direct messages.
 #[wasm_bindgen(js_name = createDirectMessage)]
-pub fn create_direct_message(options: DirectMessageOptions) -> Result<String, String> {
-    let mut builder = DirectMessageBuilder::new();
+pub fn create_direct_message(options: DirectMessageOptions) -> Result<String, ModuleError> {
+    let mut builder = DirectMessageBuilder::new().clock(JsClock);
     if let Some(from) = options.from {
         builder = builder.from(from);
     }
+    if let Some(thread_id) = options.thread_id {
+        builder = builder.thid(thread_id);
+    }
+    if let Some(parent_thread_id) = options.parent_thread_id {
+        builder = builder.pthid(parent_thread_id);
+    }
+    for (name, value) in options.headers.unwrap_or_default() {
+        builder = builder.header(name, value);
+    }
     if let Some(to) = options.to {
-        for to in to {
-            builder = builder.to(to);
-        }
+        builder = builder.to_many(to);
     }
     if let Some(created_time) = options.created_time {
         builder = builder.created_time(Some(created_time));
@@ -20,27 +43,44 @@ pub fn create_direct_message(options: DirectMessageOptions) -> Result<String, St
     if let Some(expires_time) = options.expires_time {
         builder = builder.expires_time(expires_time);
     }
+    if let Some(max_ttl) = options.max_ttl {
+        builder = builder.max_ttl(max_ttl);
+    }
     if let Some(id) = options.id {
         builder = builder.id(id);
     }
+    if let Some(lang) = options.lang {
+        builder = builder.lang(lang);
+    }
     builder = builder.message(options.message);
     builder
         .build()
-        .map_err(|e| format!("Failed to build message: {}", e))
-        .map(|msg| serde_json::to_string(&msg).unwrap())
+        .map_err(|e| ModuleError::new(e.code(), "Failed to build message", e))
+        .and_then(|msg| {
+            serde_json::to_string(&msg).map_err(|e| {
+                ModuleError::new(RESULT_SERIALIZATION_ERROR, "Failed to build message", e)
+            })
+        })
 }
 
 /// Helper function for creating a DIDComm message for media items.
 #[wasm_bindgen (js_name = createMediaItemMessage)]
-pub fn create_media_item_message(options: MediaItemsMessageOptions) -> Result<String, String> {
-    let mut builder = MediaItemsMessageBuilder::new();
+pub fn create_media_item_message(options: MediaItemsMessageOptions) -> Result<String, ModuleError> {
+    let mut builder = MediaItemsMessageBuilder::new().clock(JsClock);
     if let Some(from) = options.from {
         builder = builder.from(from);
     }
+    if let Some(thread_id) = options.thread_id {
+        builder = builder.thid(thread_id);
+    }
+    if let Some(parent_thread_id) = options.parent_thread_id {
+        builder = builder.pthid(parent_thread_id);
+    }
+    for (name, value) in options.headers.unwrap_or_default() {
+        builder = builder.header(name, value);
+    }
     if let Some(to) = options.to {
-        for to in to {
-            builder = builder.to(to);
-        }
+        builder = builder.to_many(to);
     }
     if let Some(created_time) = options.created_time {
         builder = builder.created_time(Some(created_time));
@@ -48,6 +88,9 @@ pub fn create_media_item_message(options: MediaItemsMessageOptions) -> Result<St
     if let Some(expires_time) = options.expires_time {
         builder = builder.expires_time(expires_time);
     }
+    if let Some(max_ttl) = options.max_ttl {
+        builder = builder.max_ttl(max_ttl);
+    }
     if let Some(id) = options.id {
         builder = builder.id(id);
     }
@@ -65,21 +108,34 @@ pub fn create_media_item_message(options: MediaItemsMessageOptions) -> Result<St
 
     builder
         .build()
-        .map_err(|e| format!("Failed to build message: {}", e))
-        .map(|msg| serde_json::to_string(&msg).unwrap())
+        .map_err(|e| ModuleError::new(e.code(), "Failed to build message", e))
+        .and_then(|msg| {
+            serde_json::to_string(&msg).map_err(|e| {
+                ModuleError::new(RESULT_SERIALIZATION_ERROR, "Failed to build message", e)
+            })
+        })
 }
 
 /// Helper function for creating a DIDComm message for key sharing.
 #[wasm_bindgen(js_name = createKeySharingMessage)]
-pub fn create_key_sharing_message(options: KeySharingMessageOptions) -> Result<String, String> {
-    let mut builder = KeySharingMessageBuilder::new();
+pub fn create_key_sharing_message(
+    options: KeySharingMessageOptions,
+) -> Result<String, ModuleError> {
+    let mut builder = KeySharingMessageBuilder::new().clock(JsClock);
     if let Some(from) = options.from {
         builder = builder.from(from);
     }
+    if let Some(thread_id) = options.thread_id {
+        builder = builder.thid(thread_id);
+    }
+    if let Some(parent_thread_id) = options.parent_thread_id {
+        builder = builder.pthid(parent_thread_id);
+    }
+    for (name, value) in options.headers.unwrap_or_default() {
+        builder = builder.header(name, value);
+    }
     if let Some(to) = options.to {
-        for to in to {
-            builder = builder.to(to);
-        }
+        builder = builder.to_many(to);
     }
     if let Some(created_time) = options.created_time {
         builder = builder.created_time(Some(created_time));
@@ -87,14 +143,152 @@ pub fn create_key_sharing_message(options: KeySharingMessageOptions) -> Result<S
     if let Some(expires_time) = options.expires_time {
         builder = builder.expires_time(expires_time);
     }
+    if let Some(max_ttl) = options.max_ttl {
+        builder = builder.max_ttl(max_ttl);
+    }
     if let Some(id) = options.id {
         builder = builder.id(id);
     }
     for key in options.keys {
-        builder = builder.add_key(key.into());
+        builder = builder.add_key(didcomm_module::JsonWebKey::from(key));
+    }
+    if let Some(thumbprint_kids) = options.thumbprint_kids {
+        builder = builder.thumbprint_kids(thumbprint_kids);
     }
     builder
         .build()
-        .map_err(|e| format!("Failed to build message: {}", e))
-        .map(|msg| serde_json::to_string(&msg).unwrap())
+        .map_err(|e| ModuleError::new(e.code(), "Failed to build message", e))
+        .and_then(|msg| {
+            serde_json::to_string(&msg).map_err(|e| {
+                ModuleError::new(RESULT_SERIALIZATION_ERROR, "Failed to build message", e)
+            })
+        })
+}
+
+/// Helper function for parsing a DIDComm message created by one of the helpers above.
+/// The message kind is recognised from its `type` header.
+#[wasm_bindgen(js_name = parseMessage)]
+pub fn parse_message_json(json: &str) -> Result<ParsedMessage, ModuleError> {
+    let msg: Message = serde_json::from_str(json)
+        .map_err(|e| ModuleError::new(INVALID_JSON, "Failed to parse message", e))?;
+    parse_message(msg)
+        .map_err(|e| ModuleError::new(e.code(), "Failed to parse message", e))
+        .map(Into::into)
+}
+
+/// Helper function for checking that a received DIDComm message is not expired
+/// and not created in the future.
+#[wasm_bindgen(js_name = checkFreshness)]
+pub fn check_freshness_json(options: FreshnessOptions) -> Result<(), ModuleError> {
+    let msg: Message = serde_json::from_str(&options.message)
+        .map_err(|e| ModuleError::new(INVALID_JSON, "Failed to check message freshness", e))?;
+    let now = options.now.unwrap_or_else(|| JsClock.now());
+    check_freshness(&msg, now, options.skew.unwrap_or_default())
+        .map_err(|e| ModuleError::new(e.code(), "Failed to check message freshness", e))
+}
+
+/// Helper function for splitting a DIDComm message with several recipients
+/// into copies addressed to a single recipient each, for fan-out delivery.
+#[wasm_bindgen(js_name = splitByRecipient)]
+pub fn split_by_recipient_json(json: &str) -> Result<Vec<String>, ModuleError> {
+    let msg: Message = serde_json::from_str(json)
+        .map_err(|e| ModuleError::new(INVALID_JSON, "Failed to split message", e))?;
+    split_by_recipient(&msg)
+        .iter()
+        .map(|msg| {
+            serde_json::to_string(msg).map_err(|e| {
+                ModuleError::new(RESULT_SERIALIZATION_ERROR, "Failed to split message", e)
+            })
+        })
+        .collect()
+}
+
+/// Helper function for encrypting a DIDComm message.
+/// The message is encrypted with authcrypt when `from` is set, and with anoncrypt otherwise.
+/// Sender keys are looked up in `secrets`.
+#[wasm_bindgen(js_name = packMessage, unchecked_return_type = "Promise<PackedMessage>")]
+pub fn pack_message(options: PackMessageOptions, secrets: &SecretsStore) -> Promise {
+    let secrets_resolver = secrets.resolver();
+    future_to_promise(async move {
+        let msg: Message = serde_json::from_str(&options.message)
+            .map_err(|e| ModuleError::new(INVALID_JSON, "Failed to pack message", e))?;
+        let did_resolver = InMemoryDidResolver::from(options.did_docs);
+        let packed = match options.from {
+            Some(from) => {
+                pack_authcrypt(&msg, &options.to, &from, &did_resolver, &*secrets_resolver).await
+            }
+            None => pack_anoncrypt(&msg, &options.to, &did_resolver).await,
+        };
+        let packed: PackedMessage = packed
+            .map_err(|e| ModuleError::new(e.code(), "Failed to pack message", e))?
+            .into();
+        Ok(serde_wasm_bindgen::to_value(&packed).map_err(|e| {
+            ModuleError::new(RESULT_SERIALIZATION_ERROR, "Failed to pack message", e)
+        })?)
+    })
+}
+
+/// Helper function for decrypting a DIDComm message and authenticating its sender.
+/// Recipient keys are looked up in `secrets`.
+#[wasm_bindgen(js_name = unpackMessage, unchecked_return_type = "Promise<UnpackedMessage>")]
+pub fn unpack_message(options: UnpackMessageOptions, secrets: &SecretsStore) -> Promise {
+    let secrets_resolver = secrets.resolver();
+    future_to_promise(async move {
+        let did_resolver = InMemoryDidResolver::from(options.did_docs);
+        let unpacked: UnpackedMessage = unpack(&options.message, &did_resolver, &*secrets_resolver)
+            .await
+            .map_err(|e| ModuleError::new(e.code(), "Failed to unpack message", e))?
+            .try_into()
+            .map_err(|e| {
+                ModuleError::new(RESULT_SERIALIZATION_ERROR, "Failed to unpack message", e)
+            })?;
+        Ok(serde_wasm_bindgen::to_value(&unpacked).map_err(|e| {
+            ModuleError::new(RESULT_SERIALIZATION_ERROR, "Failed to unpack message", e)
+        })?)
+    })
+}
+
+/// Helper function for generating a private key on the given curve.
+/// The `kid` of the key is its JWK thumbprint (RFC 7638).
+#[wasm_bindgen(js_name = generateKey)]
+pub fn generate_key_jwk(curve: KeyCurve) -> Result<JsonWebKey, ModuleError> {
+    generate_key(curve.into(), &mut OsRng)
+        .map(|key| didcomm_module::JsonWebKey::from(key).into())
+        .map_err(|e| ModuleError::new(e.code(), "Failed to generate key", e))
+}
+
+/// Helper function for computing the multihash of the content of a referenced media item,
+/// encoded as base58btc multibase. The hash function defaults to SHA2-256.
+#[wasm_bindgen(js_name = computeMultihash)]
+pub fn compute_multihash(content: &[u8], algorithm: Option<HashAlgorithm>) -> String {
+    multihash(algorithm.map(Into::into).unwrap_or_default(), content)
+}
+
+/// Helper function for encrypting the content of a referenced media item with a fresh random key,
+/// with AES-256-GCM by default. The ciphertext is uploaded and `ciphering` goes into the media item.
+#[wasm_bindgen(js_name = encryptContent)]
+pub fn encrypt_content_js(
+    content: &[u8],
+    algorithm: Option<CipherAlgorithm>,
+) -> Result<EncryptedContent, ModuleError> {
+    encrypt_content(
+        algorithm.map(Into::into).unwrap_or_default(),
+        content,
+        &mut OsRng,
+    )
+    .map(Into::into)
+    .map_err(|e| ModuleError::new(e.code(), "Failed to encrypt content", e))
+}
+
+/// Helper function for decrypting the downloaded content of a referenced media item with its `ciphering`.
+#[wasm_bindgen(js_name = decryptContent)]
+pub fn decrypt_content_js(ciphertext: &[u8], ciphering: Ciphering) -> Result<Vec<u8>, ModuleError> {
+    decrypt_content(ciphertext, &ciphering.into())
+        .map_err(|e| ModuleError::new(e.code(), "Failed to decrypt content", e))
+}
+
+/// Helper function for checking that downloaded content matches the multihash of its media item.
+#[wasm_bindgen(js_name = verifyHash)]
+pub fn verify_hash(content: &[u8], hash: &str) -> bool {
+    verify_multihash(hash, content)
 }
@@ -1,2 +1,7 @@
+mod clock;
+mod encrypted_content;
+mod error;
 mod functions;
+mod multihash_hasher;
+mod secrets_store;
 mod types;
@@ -0,0 +1,33 @@
+use crate::types::HashAlgorithm;
+use wasm_bindgen::prelude::*;
+
+/// Incremental hasher computing the multihash of content that is read in chunks,
+/// e.g. from a `ReadableStream`.
+#[wasm_bindgen]
+pub struct MultihashHasher {
+    hasher: didcomm_module::MultihashHasher,
+}
+
+#[wasm_bindgen]
+impl MultihashHasher {
+    /// Creates a hasher for the given hash function, SHA2-256 by default.
+    #[wasm_bindgen(constructor)]
+    pub fn new(algorithm: Option<HashAlgorithm>) -> MultihashHasher {
+        MultihashHasher {
+            hasher: didcomm_module::MultihashHasher::new(
+                algorithm.map(Into::into).unwrap_or_default(),
+            ),
+        }
+    }
+
+    /// Hashes the next chunk of the content.
+    pub fn update(&mut self, chunk: &[u8]) {
+        self.hasher.update(chunk);
+    }
+
+    /// Returns the multihash of the content, encoded as base58btc multibase.
+    /// The hasher can't be used afterwards.
+    pub fn finalize(self) -> String {
+        self.hasher.finalize()
+    }
+}
@@ -0,0 +1,53 @@
+use crate::error::{MISSING_PRIVATE_KEY, ModuleError};
+use crate::types::JsonWebKey;
+use didcomm_module::InMemorySecretsResolver;
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+
+/// Store of private keys, looked up by `kid` when decrypting and authenticating messages.
+/// Keys are registered once and the store is passed to `packMessage` and `unpackMessage`.
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct SecretsStore {
+    resolver: Rc<InMemorySecretsResolver>,
+}
+
+#[wasm_bindgen]
+impl SecretsStore {
+    /// Creates an empty store.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> SecretsStore {
+        SecretsStore::default()
+    }
+
+    /// Adds a private key, replacing any key with the same `kid`.
+    /// Fails if the key has no private key `d`.
+    #[wasm_bindgen(js_name = addKey)]
+    pub fn add_key(&mut self, key: JsonWebKey) -> Result<(), ModuleError> {
+        let key = key
+            .try_into()
+            .map_err(|e| ModuleError::new(MISSING_PRIVATE_KEY, "Failed to add key", e))?;
+        Rc::make_mut(&mut self.resolver).insert_key(key);
+        Ok(())
+    }
+
+    /// Removes the key with the given `kid`. Returns whether a key was removed.
+    #[wasm_bindgen(js_name = removeKey)]
+    pub fn remove_key(&mut self, kid: &str) -> bool {
+        Rc::make_mut(&mut self.resolver).remove_key(kid)
+    }
+
+    /// Returns whether a key with the given `kid` is known.
+    #[wasm_bindgen(js_name = hasKey)]
+    pub fn has_key(&self, kid: &str) -> bool {
+        self.resolver.contains_key(kid)
+    }
+}
+
+impl SecretsStore {
+    /// Shares the keys with a pending `packMessage` or `unpackMessage` call without copying them.
+    /// Keys added or removed meanwhile don't affect that call.
+    pub(crate) fn resolver(&self) -> Rc<InMemorySecretsResolver> {
+        Rc::clone(&self.resolver)
+    }
+}
@@ -1,7 +1,10 @@
+use didcomm_module::didcomm::did::DIDDoc;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::{collections::BTreeMap, mem};
 use tsify::Tsify;
 use wasm_bindgen::prelude::*;
+use zeroize::Zeroize;
 
 /// Options for creating a DIDComm message for direct messages.
 #[derive(Tsify, Serialize, Deserialize)]
@@ -18,14 +21,34 @@ pub struct DirectMessageOptions {
     #[tsify(optional)]
     #[serde(rename = "expiresTime")]
     pub(crate) expires_time: Option<u64>,
-    /// Recipients of the message.
+    /// Maximum time between creation (or now) and expiration of the message, in seconds.
+    /// Defaults to 365 days.
+    #[tsify(optional)]
+    #[serde(rename = "maxTtl")]
+    pub(crate) max_ttl: Option<u64>,
+    /// Recipients of the message. Duplicates are removed.
     #[tsify(optional)]
     pub(crate) to: Option<Vec<String>>,
     /// Sender of the message.
     #[tsify(optional)]
     pub(crate) from: Option<String>,
+    /// ID of the thread the message belongs to (`thid`).
+    #[tsify(optional)]
+    #[serde(rename = "threadId")]
+    pub(crate) thread_id: Option<String>,
+    /// ID of the parent thread of the message (`pthid`).
+    #[tsify(optional)]
+    #[serde(rename = "parentThreadId")]
+    pub(crate) parent_thread_id: Option<String>,
+    /// Custom top-level headers of the message, e.g. application metadata.
+    /// Reserved DIDComm header names are rejected.
+    #[tsify(optional, type = "Record<string, unknown>")]
+    pub(crate) headers: Option<BTreeMap<String, Value>>,
     /// Content of the message.
     pub(crate) message: String,
+    /// Language of the content as a BCP 47 tag, e.g. "en".
+    #[tsify(optional)]
+    pub(crate) lang: Option<String>,
 }
 
 /// Options for creating a DIDComm message for key sharing.
@@ -44,16 +67,38 @@ pub struct KeySharingMessageOptions {
     #[tsify(optional)]
     #[serde(rename = "expiresTime")]
     pub(crate) expires_time: Option<u64>,
-    /// Recipients of the message.
+    /// Maximum time between creation (or now) and expiration of the message, in seconds.
+    /// Defaults to 365 days.
+    #[tsify(optional)]
+    #[serde(rename = "maxTtl")]
+    pub(crate) max_ttl: Option<u64>,
+    /// Recipients of the message. Duplicates are removed.
     #[tsify(optional)]
     pub(crate) to: Option<Vec<String>>,
     /// Sender of the message.
     #[tsify(optional)]
     pub(crate) from: Option<String>,
+    /// ID of the thread the message belongs to (`thid`).
+    #[tsify(optional)]
+    #[serde(rename = "threadId")]
+    pub(crate) thread_id: Option<String>,
+    /// ID of the parent thread of the message (`pthid`).
+    #[tsify(optional)]
+    #[serde(rename = "parentThreadId")]
+    pub(crate) parent_thread_id: Option<String>,
+    /// Custom top-level headers of the message, e.g. application metadata.
+    /// Reserved DIDComm header names are rejected.
+    #[tsify(optional, type = "Record<string, unknown>")]
+    pub(crate) headers: Option<BTreeMap<String, Value>>,
     /// Json Web Keys to be shared.
     pub(crate) keys: Vec<JsonWebKey>,
+    /// Fill empty key IDs with the JWK thumbprint of the key (RFC 7638) and reject key IDs that differ from it.
+    #[tsify(optional)]
+    #[serde(rename = "thumbprintKids")]
+    pub(crate) thumbprint_kids: Option<bool>,
 }
 
+/// JSON Web Key. The private key `d` is zeroized when the key is dropped.
 #[derive(Tsify, Serialize, Deserialize)]
 #[tsify(into_wasm_abi, from_wasm_abi)]
 pub struct JsonWebKey {
@@ -63,10 +108,14 @@ pub struct JsonWebKey {
     pub crv: String,
     /// x-coordinate
     pub x: String,
-    /// y-coordinate
-    pub y: String,
-    /// Private key
-    pub d: String,
+    /// y-coordinate, absent for OKP keys
+    #[tsify(optional)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub y: Option<String>,
+    /// Private key, absent for public keys
+    #[tsify(optional)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub d: Option<String>,
     /// Key usage
     #[serde(rename = "use")]
     pub _use: String,
@@ -74,18 +123,104 @@ pub struct JsonWebKey {
     pub kid: String,
 }
 
+/// Curve of a generated key.
+#[derive(Tsify, Serialize, Deserialize)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub enum KeyCurve {
+    /// NIST P-256 key for key agreement.
+    #[serde(rename = "P-256")]
+    P256,
+    /// X25519 key for key agreement.
+    X25519,
+    /// Ed25519 key for signing.
+    Ed25519,
+    /// secp256k1 key for signing.
+    #[serde(rename = "secp256k1")]
+    Secp256k1,
+}
+
+/// Hash function of a multihash.
+#[derive(Tsify, Serialize, Deserialize)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub enum HashAlgorithm {
+    /// SHA2-256.
+    #[serde(rename = "sha2-256")]
+    Sha2_256,
+    /// BLAKE2b with a 256 bit digest.
+    #[serde(rename = "blake2b-256")]
+    Blake2b256,
+    /// SHA3-256.
+    #[serde(rename = "sha3-256")]
+    Sha3_256,
+}
+
+/// Authenticated cipher of the content of a referenced media item.
+#[derive(Tsify, Serialize, Deserialize, Clone, Copy)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub enum CipherAlgorithm {
+    /// AES-256-GCM with a 96 bit IV, as in the Web Crypto API.
+    #[serde(rename = "AES-GCM")]
+    Aes256Gcm,
+    /// XChaCha20-Poly1305 with a 192 bit nonce.
+    #[serde(rename = "XChaCha20-Poly1305")]
+    XChaCha20Poly1305,
+}
+
+/// Key and IV of a supported cipher suite.
+#[derive(Tsify, Serialize, Deserialize, Clone)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct CipheringParameters {
+    /// Hex encoded initialization vector, or nonce.
+    pub iv: String,
+    /// Hex encoded 256 bit content encryption key.
+    pub key: String,
+}
+
+/// Encryption information of a referenced media item.
+#[derive(Tsify, Serialize, Deserialize, Clone)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+#[serde(untagged)]
+pub enum Ciphering {
+    /// Supported cipher suite.
+    Supported {
+        /// Cipher the content is encrypted with.
+        algorithm: CipherAlgorithm,
+        /// Key and IV the content is encrypted with.
+        parameters: CipheringParameters,
+    },
+    /// Encryption information of an unsupported algorithm, e.g. from older messages, kept as is.
+    Unknown(
+        #[tsify(
+            type = "{ algorithm?: string; parameters?: Record<string, unknown>; [key: string]: unknown }"
+        )]
+        Value,
+    ),
+}
+
 #[derive(Tsify, Serialize, Deserialize)]
 #[tsify(into_wasm_abi, from_wasm_abi)]
 pub struct MediaItemsMessageOptions {
     /// ID of the message.
     #[tsify(optional)]
     pub(crate) id: Option<String>,
-    /// Recipients of the message.
+    /// Recipients of the message. Duplicates are removed.
     #[tsify(optional)]
     pub(crate) to: Option<Vec<String>>,
     /// Sender of the message.
     #[tsify(optional)]
     pub(crate) from: Option<String>,
+    /// ID of the thread the message belongs to (`thid`).
+    #[tsify(optional)]
+    #[serde(rename = "threadId")]
+    pub(crate) thread_id: Option<String>,
+    /// ID of the parent thread of the message (`pthid`).
+    #[tsify(optional)]
+    #[serde(rename = "parentThreadId")]
+    pub(crate) parent_thread_id: Option<String>,
+    /// Custom top-level headers of the message, e.g. application metadata.
+    /// Reserved DIDComm header names are rejected.
+    #[tsify(optional, type = "Record<string, unknown>")]
+    pub(crate) headers: Option<BTreeMap<String, Value>>,
     /// Creation time of the message.
     #[tsify(optional)]
     #[serde(rename = "createdTime")]
@@ -94,6 +229,11 @@ pub struct MediaItemsMessageOptions {
     #[tsify(optional)]
     #[serde(rename = "expiresTime")]
     pub(crate) expires_time: Option<u64>,
+    /// Maximum time between creation (or now) and expiration of the message, in seconds.
+    /// Defaults to 365 days.
+    #[tsify(optional)]
+    #[serde(rename = "maxTtl")]
+    pub(crate) max_ttl: Option<u64>,
     /// List of media items.
     #[serde(rename = "mediaItems")]
     pub(crate) media_items: Vec<MediaItem>,
@@ -118,18 +258,32 @@ pub struct MediaItemReferenced {
     pub media_type: String,
     /// File name
     #[tsify(optional)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub filename: Option<String>,
     /// File description
     #[tsify(optional)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     /// Reference
     pub link: String,
     /// The hash of the content encoded in multi-hash format. Used as an integrity check for the attachment.
     pub hash: String,
-    /// Encryption information
-    #[tsify(type = "{algorithm: string, parameters: Record<string, unknown>}")]
+    /// Encryption information of the content behind `link`, see `encryptContent`.
+    #[tsify(optional)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ciphering: Option<Ciphering>,
+    /// Format of the file if the media type is not sufficient.
     #[tsify(optional)]
-    pub ciphering: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// Last modification time of the file, in seconds since the Unix epoch.
+    #[tsify(optional)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lastmod_time: Option<u64>,
+    /// Size of the file in bytes.
+    #[tsify(optional)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub byte_count: Option<u64>,
 }
 // / Represents a media item with inline content in a DIDComm message.
 #[derive(Tsify, Serialize, Deserialize)]
@@ -141,12 +295,226 @@ pub struct MediaItemInlined {
     pub media_type: String,
     /// File name.
     #[tsify(optional)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub filename: Option<String>,
     /// File description.
     #[tsify(optional)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     /// File contents encoded as base64.
     pub base64: String,
+    /// Format of the file if the media type is not sufficient.
+    #[tsify(optional)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// Last modification time of the file, in seconds since the Unix epoch.
+    #[tsify(optional)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lastmod_time: Option<u64>,
+    /// Size of the file in bytes. Computed from `base64` when the message is created.
+    #[tsify(optional)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub byte_count: Option<u64>,
+}
+
+/// DIDComm message parsed according to its `type` header.
+#[derive(Tsify, Serialize, Deserialize)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ParsedMessage {
+    Direct(DirectMessage),
+    MediaItems(MediaItemsMessage),
+    KeySharing(KeySharingMessage),
+}
+
+/// Parsed direct message.
+#[derive(Tsify, Serialize, Deserialize)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct DirectMessage {
+    /// ID of the message.
+    pub(crate) id: String,
+    /// Creation time of the message.
+    #[tsify(optional)]
+    #[serde(rename = "createdTime", skip_serializing_if = "Option::is_none")]
+    pub(crate) created_time: Option<u64>,
+    /// Expiration time of the message.
+    #[tsify(optional)]
+    #[serde(rename = "expiresTime", skip_serializing_if = "Option::is_none")]
+    pub(crate) expires_time: Option<u64>,
+    /// Recipients of the message.
+    #[tsify(optional)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) to: Option<Vec<String>>,
+    /// Sender of the message.
+    #[tsify(optional)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) from: Option<String>,
+    /// ID of the thread of the message (`thid`).
+    #[tsify(optional)]
+    #[serde(rename = "threadId", skip_serializing_if = "Option::is_none")]
+    pub(crate) thread_id: Option<String>,
+    /// ID of the parent thread of the message (`pthid`).
+    #[tsify(optional)]
+    #[serde(rename = "parentThreadId", skip_serializing_if = "Option::is_none")]
+    pub(crate) parent_thread_id: Option<String>,
+    /// Content of the message.
+    pub(crate) message: String,
+    /// Language of the content as a BCP 47 tag.
+    #[tsify(optional)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) lang: Option<String>,
+}
+
+/// Parsed key sharing message.
+#[derive(Tsify, Serialize, Deserialize)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct KeySharingMessage {
+    /// ID of the message.
+    pub(crate) id: String,
+    /// Creation time of the message.
+    #[tsify(optional)]
+    #[serde(rename = "createdTime", skip_serializing_if = "Option::is_none")]
+    pub(crate) created_time: Option<u64>,
+    /// Expiration time of the message.
+    #[tsify(optional)]
+    #[serde(rename = "expiresTime", skip_serializing_if = "Option::is_none")]
+    pub(crate) expires_time: Option<u64>,
+    /// Recipients of the message.
+    #[tsify(optional)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) to: Option<Vec<String>>,
+    /// Sender of the message.
+    #[tsify(optional)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) from: Option<String>,
+    /// ID of the thread of the message (`thid`).
+    #[tsify(optional)]
+    #[serde(rename = "threadId", skip_serializing_if = "Option::is_none")]
+    pub(crate) thread_id: Option<String>,
+    /// ID of the parent thread of the message (`pthid`).
+    #[tsify(optional)]
+    #[serde(rename = "parentThreadId", skip_serializing_if = "Option::is_none")]
+    pub(crate) parent_thread_id: Option<String>,
+    /// Shared Json Web Keys.
+    pub(crate) keys: Vec<JsonWebKey>,
+}
+
+/// Parsed media items message.
+#[derive(Tsify, Serialize, Deserialize)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct MediaItemsMessage {
+    /// ID of the message.
+    pub(crate) id: String,
+    /// Creation time of the message.
+    #[tsify(optional)]
+    #[serde(rename = "createdTime", skip_serializing_if = "Option::is_none")]
+    pub(crate) created_time: Option<u64>,
+    /// Expiration time of the message.
+    #[tsify(optional)]
+    #[serde(rename = "expiresTime", skip_serializing_if = "Option::is_none")]
+    pub(crate) expires_time: Option<u64>,
+    /// Recipients of the message.
+    #[tsify(optional)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) to: Option<Vec<String>>,
+    /// Sender of the message.
+    #[tsify(optional)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) from: Option<String>,
+    /// ID of the thread of the message (`thid`).
+    #[tsify(optional)]
+    #[serde(rename = "threadId", skip_serializing_if = "Option::is_none")]
+    pub(crate) thread_id: Option<String>,
+    /// ID of the parent thread of the message (`pthid`).
+    #[tsify(optional)]
+    #[serde(rename = "parentThreadId", skip_serializing_if = "Option::is_none")]
+    pub(crate) parent_thread_id: Option<String>,
+    /// List of media items, in the order of the message body.
+    #[serde(rename = "mediaItems")]
+    pub(crate) media_items: Vec<MediaItem>,
+}
+
+/// Options for encrypting a DIDComm message.
+#[derive(Tsify, Serialize, Deserialize)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct PackMessageOptions {
+    /// DIDComm message as a JSON string, as returned by the helpers creating messages.
+    pub(crate) message: String,
+    /// Recipient DID or key ID.
+    pub(crate) to: String,
+    /// Sender DID or key ID. The message is encrypted anonymously when omitted.
+    #[tsify(optional)]
+    pub(crate) from: Option<String>,
+    /// DID documents of the recipient and, for authenticated encryption, of the sender.
+    #[serde(rename = "didDocs")]
+    #[tsify(type = "Record<string, unknown>[]")]
+    pub(crate) did_docs: Vec<DIDDoc>,
+}
+
+/// Options for checking that a received DIDComm message is not expired.
+#[derive(Tsify, Serialize, Deserialize)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct FreshnessOptions {
+    /// DIDComm message as a JSON string.
+    pub(crate) message: String,
+    /// Current time in seconds since the Unix epoch. Defaults to `Date.now()`.
+    #[tsify(optional)]
+    pub(crate) now: Option<u64>,
+    /// Allowed clock difference between sender and receiver in seconds. Defaults to 0.
+    #[tsify(optional)]
+    pub(crate) skew: Option<u64>,
+}
+
+/// Encrypted DIDComm message.
+#[derive(Tsify, Serialize, Deserialize)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct PackedMessage {
+    /// Encrypted message (JWE) as a JSON string.
+    pub(crate) message: String,
+    /// Key ID of the sender key used for encryption.
+    #[tsify(optional)]
+    #[serde(rename = "fromKid", skip_serializing_if = "Option::is_none")]
+    pub(crate) from_kid: Option<String>,
+    /// Key IDs of the recipient keys the message is encrypted for.
+    #[serde(rename = "toKids")]
+    pub(crate) to_kids: Vec<String>,
+}
+
+/// Options for decrypting a DIDComm message.
+#[derive(Tsify, Serialize, Deserialize)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct UnpackMessageOptions {
+    /// Encrypted, signed or plaintext DIDComm message as a JSON string.
+    pub(crate) message: String,
+    /// DID documents of the recipient and, for authenticated messages, of the sender.
+    #[serde(rename = "didDocs")]
+    #[tsify(type = "Record<string, unknown>[]")]
+    pub(crate) did_docs: Vec<DIDDoc>,
+}
+
+/// Decrypted DIDComm message with the security properties of its envelope.
+#[derive(Tsify, Serialize, Deserialize)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct UnpackedMessage {
+    /// Plaintext message as a JSON string.
+    pub(crate) message: String,
+    /// Whether the message was encrypted.
+    pub(crate) encrypted: bool,
+    /// Whether the sender was authenticated.
+    pub(crate) authenticated: bool,
+    /// Whether the message was signed.
+    pub(crate) signed: bool,
+    /// Key ID of the sender key used for encryption or signature.
+    #[tsify(optional)]
+    #[serde(rename = "fromKid", skip_serializing_if = "Option::is_none")]
+    pub(crate) from_kid: Option<String>,
+    /// Key IDs of the recipient keys the message was encrypted for.
+    #[serde(rename = "toKids")]
+    pub(crate) to_kids: Vec<String>,
+    /// DID of the authenticated sender.
+    #[tsify(optional)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) sender: Option<String>,
 }
 
 impl From<MediaItemReferenced> for didcomm_module::MediaItemReferenced {
@@ -158,7 +526,10 @@ impl From<MediaItemReferenced> for didcomm_module::MediaItemReferenced {
             description: val.description,
             link: val.link,
             hash: val.hash,
-            ciphering: val.ciphering,
+            ciphering: val.ciphering.map(Into::into),
+            format: val.format,
+            lastmod_time: val.lastmod_time,
+            byte_count: val.byte_count,
         }
     }
 }
@@ -171,20 +542,267 @@ impl From<MediaItemInlined> for didcomm_module::MediaItemInlined {
             filename: val.filename,
             description: val.description,
             base64: val.base64,
+            format: val.format,
+            lastmod_time: val.lastmod_time,
+            byte_count: val.byte_count,
         }
     }
 }
 
+impl Drop for JsonWebKey {
+    fn drop(&mut self) {
+        self.d.zeroize();
+    }
+}
+
 impl From<JsonWebKey> for didcomm_module::JsonWebKey {
-    fn from(val: JsonWebKey) -> Self {
-        didcomm_module::JsonWebKey {
-            kty: val.kty,
-            crv: val.crv,
-            x: val.x,
-            y: val.y,
-            d: val.d,
-            use_: val._use,
-            kid: val.kid,
+    fn from(mut val: JsonWebKey) -> Self {
+        // Fields are taken rather than moved since the key zeroizes `d` on drop.
+        let public = didcomm_module::PublicJwk {
+            kty: mem::take(&mut val.kty),
+            crv: mem::take(&mut val.crv),
+            x: mem::take(&mut val.x),
+            y: val.y.take(),
+            use_: mem::take(&mut val._use),
+            kid: mem::take(&mut val.kid),
+        };
+        match val.d.take() {
+            Some(d) => didcomm_module::PrivateJwk::new(public, d).into(),
+            None => public.into(),
+        }
+    }
+}
+
+impl From<KeyCurve> for didcomm_module::KeyCurve {
+    fn from(val: KeyCurve) -> Self {
+        match val {
+            KeyCurve::P256 => didcomm_module::KeyCurve::P256,
+            KeyCurve::X25519 => didcomm_module::KeyCurve::X25519,
+            KeyCurve::Ed25519 => didcomm_module::KeyCurve::Ed25519,
+            KeyCurve::Secp256k1 => didcomm_module::KeyCurve::Secp256k1,
+        }
+    }
+}
+
+impl From<HashAlgorithm> for didcomm_module::HashAlgorithm {
+    fn from(val: HashAlgorithm) -> Self {
+        match val {
+            HashAlgorithm::Sha2_256 => didcomm_module::HashAlgorithm::Sha2_256,
+            HashAlgorithm::Blake2b256 => didcomm_module::HashAlgorithm::Blake2b256,
+            HashAlgorithm::Sha3_256 => didcomm_module::HashAlgorithm::Sha3_256,
+        }
+    }
+}
+
+impl From<CipherAlgorithm> for didcomm_module::CipherAlgorithm {
+    fn from(val: CipherAlgorithm) -> Self {
+        match val {
+            CipherAlgorithm::Aes256Gcm => didcomm_module::CipherAlgorithm::Aes256Gcm,
+            CipherAlgorithm::XChaCha20Poly1305 => {
+                didcomm_module::CipherAlgorithm::XChaCha20Poly1305
+            }
+        }
+    }
+}
+
+impl From<didcomm_module::CipherAlgorithm> for CipherAlgorithm {
+    fn from(val: didcomm_module::CipherAlgorithm) -> Self {
+        match val {
+            didcomm_module::CipherAlgorithm::Aes256Gcm => CipherAlgorithm::Aes256Gcm,
+            didcomm_module::CipherAlgorithm::XChaCha20Poly1305 => {
+                CipherAlgorithm::XChaCha20Poly1305
+            }
+        }
+    }
+}
+
+impl From<Ciphering> for didcomm_module::Ciphering {
+    fn from(val: Ciphering) -> Self {
+        match val {
+            Ciphering::Supported {
+                algorithm,
+                parameters,
+            } => didcomm_module::Ciphering::new(
+                algorithm.into(),
+                didcomm_module::CipheringParameters {
+                    iv: parameters.iv,
+                    key: parameters.key,
+                },
+            ),
+            Ciphering::Unknown(value) => value.into(),
+        }
+    }
+}
+
+impl From<didcomm_module::Ciphering> for Ciphering {
+    fn from(val: didcomm_module::Ciphering) -> Self {
+        match val {
+            didcomm_module::Ciphering::Aes256Gcm(parameters) => Ciphering::Supported {
+                algorithm: CipherAlgorithm::Aes256Gcm,
+                parameters: parameters.into(),
+            },
+            didcomm_module::Ciphering::XChaCha20Poly1305(parameters) => Ciphering::Supported {
+                algorithm: CipherAlgorithm::XChaCha20Poly1305,
+                parameters: parameters.into(),
+            },
+            didcomm_module::Ciphering::Unknown(value) => Ciphering::Unknown(value),
+        }
+    }
+}
+
+impl From<didcomm_module::CipheringParameters> for CipheringParameters {
+    fn from(val: didcomm_module::CipheringParameters) -> Self {
+        CipheringParameters {
+            iv: val.iv,
+            key: val.key,
+        }
+    }
+}
+
+impl TryFrom<JsonWebKey> for didcomm_module::PrivateJwk {
+    type Error = String;
+
+    fn try_from(val: JsonWebKey) -> Result<Self, Self::Error> {
+        match val.into() {
+            didcomm_module::JsonWebKey::Private(key) => Ok(key),
+            didcomm_module::JsonWebKey::Public(key) => {
+                Err(format!("Key {} has no private key", key.kid))
+            }
         }
     }
 }
+
+impl From<didcomm_module::MediaItemReferenced> for MediaItemReferenced {
+    fn from(val: didcomm_module::MediaItemReferenced) -> Self {
+        MediaItemReferenced {
+            id: val.id,
+            media_type: val.media_type,
+            filename: val.filename,
+            description: val.description,
+            link: val.link,
+            hash: val.hash,
+            ciphering: val.ciphering.map(Into::into),
+            format: val.format,
+            lastmod_time: val.lastmod_time,
+            byte_count: val.byte_count,
+        }
+    }
+}
+
+impl From<didcomm_module::MediaItemInlined> for MediaItemInlined {
+    fn from(val: didcomm_module::MediaItemInlined) -> Self {
+        MediaItemInlined {
+            id: val.id,
+            media_type: val.media_type,
+            filename: val.filename,
+            description: val.description,
+            base64: val.base64,
+            format: val.format,
+            lastmod_time: val.lastmod_time,
+            byte_count: val.byte_count,
+        }
+    }
+}
+
+impl From<didcomm_module::MediaItem> for MediaItem {
+    fn from(val: didcomm_module::MediaItem) -> Self {
+        match val {
+            didcomm_module::MediaItem::Referenced(media_item_referenced) => {
+                MediaItem::Referenced(media_item_referenced.into())
+            }
+            didcomm_module::MediaItem::Inlined(media_item_inlined) => {
+                MediaItem::Inlined(media_item_inlined.into())
+            }
+        }
+    }
+}
+
+impl From<didcomm_module::JsonWebKey> for JsonWebKey {
+    fn from(val: didcomm_module::JsonWebKey) -> Self {
+        // `d` is moved out of the key rather than copied; `JsonWebKey` zeroizes it on drop.
+        let (public, d) = match val {
+            didcomm_module::JsonWebKey::Private(key) => {
+                let (public, mut d) = key.into_parts();
+                (public, Some(mem::take(&mut *d)))
+            }
+            didcomm_module::JsonWebKey::Public(key) => (key, None),
+        };
+        JsonWebKey {
+            kty: public.kty,
+            crv: public.crv,
+            x: public.x,
+            y: public.y,
+            d,
+            _use: public.use_,
+            kid: public.kid,
+        }
+    }
+}
+
+impl From<didcomm_module::ParsedMessage> for ParsedMessage {
+    fn from(val: didcomm_module::ParsedMessage) -> Self {
+        match val {
+            didcomm_module::ParsedMessage::Direct(msg) => ParsedMessage::Direct(DirectMessage {
+                id: msg.id,
+                created_time: msg.created_time,
+                expires_time: msg.expires_time,
+                to: msg.to,
+                from: msg.from,
+                thread_id: msg.thid,
+                parent_thread_id: msg.pthid,
+                message: msg.message,
+                lang: msg.lang,
+            }),
+            didcomm_module::ParsedMessage::KeySharing(msg) => {
+                ParsedMessage::KeySharing(KeySharingMessage {
+                    id: msg.id,
+                    created_time: msg.created_time,
+                    expires_time: msg.expires_time,
+                    to: msg.to,
+                    from: msg.from,
+                    thread_id: msg.thid,
+                    parent_thread_id: msg.pthid,
+                    keys: msg.keys.into_iter().map(Into::into).collect(),
+                })
+            }
+            didcomm_module::ParsedMessage::MediaItems(msg) => {
+                ParsedMessage::MediaItems(MediaItemsMessage {
+                    id: msg.id,
+                    created_time: msg.created_time,
+                    expires_time: msg.expires_time,
+                    to: msg.to,
+                    from: msg.from,
+                    thread_id: msg.thid,
+                    parent_thread_id: msg.pthid,
+                    media_items: msg.media_items.into_iter().map(Into::into).collect(),
+                })
+            }
+        }
+    }
+}
+
+impl From<didcomm_module::PackedMessage> for PackedMessage {
+    fn from(val: didcomm_module::PackedMessage) -> Self {
+        PackedMessage {
+            message: val.message,
+            from_kid: val.from_kid,
+            to_kids: val.to_kids,
+        }
+    }
+}
+
+impl TryFrom<didcomm_module::UnpackedMessage> for UnpackedMessage {
+    type Error = serde_json::Error;
+
+    fn try_from(val: didcomm_module::UnpackedMessage) -> Result<Self, Self::Error> {
+        Ok(UnpackedMessage {
+            message: serde_json::to_string(&val.message)?,
+            encrypted: val.encrypted,
+            authenticated: val.authenticated,
+            signed: val.signed,
+            from_kid: val.from_kid,
+            to_kids: val.to_kids,
+            sender: val.sender,
+        })
+    }
+}
@@ -1,5 +1,5 @@
 import { expect, test } from "@jest/globals";
-import { createDirectMessage, createKeySharingMessage, createMediaItemMessage } from "message-module-node";
+import { checkFreshness, computeMultihash, createDirectMessage, createKeySharingMessage, createMediaItemMessage, decryptContent, encryptContent, generateKey, MultihashHasher, parseMessage, splitByRecipient, verifyHash } from "message-module-node";
 
 test("direct message builder", () => {
     let directMessageString = createDirectMessage({
@@ -24,6 +24,44 @@ test("direct message builder", () => {
     expect(JSON.parse(directMessageString)).toEqual(expected);
 });
 
+test("direct message builder with thread", () => {
+    let directMessageString = createDirectMessage({
+        id: "test-id-2",
+        threadId: "test-id-1",
+        parentThreadId: "thread-1",
+        message: "test reply"
+    });
+    const message = JSON.parse(directMessageString);
+    expect(message.thid).toEqual("test-id-1");
+    expect(message.pthid).toEqual("thread-1");
+
+    const parsed = parseMessage(directMessageString);
+    expect(parsed.kind === "direct" && parsed.threadId).toEqual("test-id-1");
+    expect(parsed.kind === "direct" && parsed.parentThreadId).toEqual("thread-1");
+});
+
+test("direct message builder with language", () => {
+    let directMessageString = createDirectMessage({ id: "test-id-1", lang: "en", message: "test message" });
+    expect(JSON.parse(directMessageString).lang).toEqual("en");
+    expect(parseMessage(directMessageString)).toEqual({ kind: "direct", id: "test-id-1", message: "test message", lang: "en" });
+
+    expect(() => createDirectMessage({ lang: "en_US", message: "test message" }))
+        .toThrow("Failed to build message: Invalid BCP 47 language tag: en_US");
+});
+
+test("direct message builder with headers", () => {
+    let directMessageString = createDirectMessage({
+        message: "test message",
+        headers: { bucket_id: 7, tags: ["chat"] }
+    });
+    const message = JSON.parse(directMessageString);
+    expect(message.bucket_id).toEqual(7);
+    expect(message.tags).toEqual(["chat"]);
+
+    expect(() => createDirectMessage({ message: "test message", headers: { thid: "thread-1" } }))
+        .toThrow("Failed to build message: Reserved header can't be set as a custom header: thid");
+});
+
 test("key sharing builder", () => {
     let keySharingMessageString = createKeySharingMessage({
         id: "test-id-1",
@@ -35,18 +73,18 @@ test("key sharing builder", () => {
             {
                 kty: "EC",
                 crv: "P-256",
-                x: "0000",
-                y: "0001",
-                d: "0002",
+                x: "2syLh57B-dGpa0F8p1JrO6JU7UUSF6j7qL-vfk1eOoY",
+                y: "BgsGtI7UPsObMRjdElxLOrgAO9JggNMjOcfzEPox18w",
+                d: "7TCIdt1rhThFtWcEiLnk_COEjh1ZfQhM4bW2wz-dp4A",
                 use: "enc",
                 kid: "key-id"
             },
             {
                 kty: "EC",
                 crv: "P-256",
-                x: "0003",
-                y: "0004",
-                d: "0005",
+                x: "L0crjMN1g0Ih4sYAJ_nGoHUck2cloltUpUVQDhF2nHE",
+                y: "SxYgE7CmEJYi7IDhgK5jI4ZiajO8jPRZDldVhqFpYoo",
+                d: "sB0bYtpaXyp-h17dDpMx91N3Du1AdN4z1FUq02GbmLw",
                 use: "enc",
                 kid: "key-id-2"
             }
@@ -58,8 +96,8 @@ test("key sharing builder", () => {
         type: "https://didcomm.org/key-sharing/1.0/send-keys",
         body: {
             keys: [
-                { crv: "P-256", d: "0002", kid: "key-id", kty: "EC", use: "enc", x: "0000", y: "0001" },
-                { crv: "P-256", d: "0005", kid: "key-id-2", kty: "EC", use: "enc", x: "0003", y: "0004" }
+                { crv: "P-256", d: "7TCIdt1rhThFtWcEiLnk_COEjh1ZfQhM4bW2wz-dp4A", kid: "key-id", kty: "EC", use: "enc", x: "2syLh57B-dGpa0F8p1JrO6JU7UUSF6j7qL-vfk1eOoY", y: "BgsGtI7UPsObMRjdElxLOrgAO9JggNMjOcfzEPox18w" },
+                { crv: "P-256", d: "sB0bYtpaXyp-h17dDpMx91N3Du1AdN4z1FUq02GbmLw", kid: "key-id-2", kty: "EC", use: "enc", x: "L0crjMN1g0Ih4sYAJ_nGoHUck2cloltUpUVQDhF2nHE", y: "SxYgE7CmEJYi7IDhgK5jI4ZiajO8jPRZDldVhqFpYoo" }
             ]
         },
         from: "did:example:test1",
@@ -70,6 +108,31 @@ test("key sharing builder", () => {
     expect(JSON.parse(keySharingMessageString)).toEqual(expected);
 });
 
+test("key sharing builder with public OKP key", () => {
+    let keySharingMessageString = createKeySharingMessage({
+        id: "test-id-1",
+        keys: [{ kty: "OKP", crv: "X25519", x: "GDTrI66K0pFfO54tlCSvfjjNapIs44dzpneBgyx0S3E", use: "enc", kid: "key-id" }]
+    });
+    expect(JSON.parse(keySharingMessageString).body).toEqual({
+        keys: [{ kty: "OKP", crv: "X25519", x: "GDTrI66K0pFfO54tlCSvfjjNapIs44dzpneBgyx0S3E", use: "enc", kid: "key-id" }]
+    });
+});
+
+test("key sharing builder with thumbprint key IDs", () => {
+    let keySharingMessageString = createKeySharingMessage({
+        id: "test-id-1",
+        keys: [{ kty: "OKP", crv: "Ed25519", x: "11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo", use: "sig", kid: "" }],
+        thumbprintKids: true
+    });
+    expect(JSON.parse(keySharingMessageString).body.keys[0].kid).toEqual("kPrK_qmxVWaYVA9wwBF6Iuo3vVzz7TxHCTwXBygrS4k");
+});
+
+test("key sharing builder rejects invalid key", () => {
+    expect(() => createKeySharingMessage({
+        keys: [{ kty: "OKP", crv: "X25519", x: "0000", use: "enc", kid: "key-id" }]
+    })).toThrow("Failed to build message: Key key-id: field x has an invalid length");
+});
+
 test("create media item builder", () => {
     const mediaItemsMessageString = createMediaItemMessage({
         id: "test-id-1",
@@ -80,23 +143,24 @@ test("create media item builder", () => {
         mediaItems: [
             {
                 id: "media-item-id-1",
-                media_type: "pdf",
+                media_type: "application/pdf",
                 filename: "test.pdf",
                 description: "document for test",
                 link: "ipfs://example-link/test.pdf",
                 hash: "xyz..",
+                byte_count: 1024,
                 ciphering: {
                     algorithm: "AES-GCM",
                     parameters: {
-                        iv: "test-iv",
-                        key: "test-key"
+                        iv: "2f3849399c60cb04b923bd33",
+                        key: "233f8ce4ac6aa125927ccd98af5750d08c9c61d98a3f5d43cbf096b4caaebe80"
                     }
                 }
             },
             {
                 id: "media-item-id-1",
-                media_type: "pdf",
-                base64: "test-base64"
+                media_type: "text/plain",
+                base64: "dGVzdA=="
             }
         ]
     });
@@ -119,8 +183,8 @@ test("create media item builder", () => {
                     ciphering: {
                         algorithm: "AES-GCM",
                         parameters: {
-                            iv: "test-iv",
-                            key: "test-key"
+                            iv: "2f3849399c60cb04b923bd33",
+                            key: "233f8ce4ac6aa125927ccd98af5750d08c9c61d98a3f5d43cbf096b4caaebe80"
                         }
                     }
                 }
@@ -133,10 +197,11 @@ test("create media item builder", () => {
         attachments: [
             {
                 data: {
-                    base64: "test-base64"
+                    base64: "dGVzdA=="
                 },
                 id: "media-item-id-1",
-                media_type: "pdf"
+                media_type: "text/plain",
+                byte_count: 4
             },
             {
                 data: {
@@ -146,7 +211,8 @@ test("create media item builder", () => {
                 id: "media-item-id-1",
                 description: "document for test",
                 filename: "test.pdf",
-                media_type: "pdf"
+                media_type: "application/pdf",
+                byte_count: 1024
             }
         ]
     };
@@ -164,8 +230,8 @@ test("create media item builder, one item", () => {
         mediaItems: [
             {
                 id: "media-item-id-1",
-                media_type: "pdf",
-                base64: "test-base64"
+                media_type: "text/plain",
+                base64: "dGVzdA=="
             }
         ]
     });
@@ -191,10 +257,11 @@ test("create media item builder, one item", () => {
         attachments: [
             {
                 data: {
-                    base64: "test-base64"
+                    base64: "dGVzdA=="
                 },
                 id: "media-item-id-1",
-                media_type: "pdf"
+                media_type: "text/plain",
+                byte_count: 4
             },
         ]
     };
@@ -212,7 +279,7 @@ test("create media item, one reference item", () => {
         mediaItems: [
             {
                 id: "media-item-id-1",
-                media_type: "pdf",
+                media_type: "application/pdf",
                 filename: "test.pdf",
                 description: "document for test",
                 link: "ipfs://example-link/test.pdf",
@@ -220,8 +287,8 @@ test("create media item, one reference item", () => {
                 ciphering: {
                     algorithm: "AES-GCM",
                     parameters: {
-                        iv: "test-iv",
-                        key: "test-key"
+                        iv: "2f3849399c60cb04b923bd33",
+                        key: "233f8ce4ac6aa125927ccd98af5750d08c9c61d98a3f5d43cbf096b4caaebe80"
                     }
                 }
             },
@@ -242,8 +309,8 @@ test("create media item, one reference item", () => {
                     ciphering: {
                         algorithm: "AES-GCM",
                         parameters: {
-                            iv: "test-iv",
-                            key: "test-key"
+                            iv: "2f3849399c60cb04b923bd33",
+                            key: "233f8ce4ac6aa125927ccd98af5750d08c9c61d98a3f5d43cbf096b4caaebe80"
                         }
                     }
                 }
@@ -262,10 +329,183 @@ test("create media item, one reference item", () => {
                 id: "media-item-id-1",
                 description: "document for test",
                 filename: "test.pdf",
-                media_type: "pdf"
+                media_type: "application/pdf"
             }
         ]
     };
 
     expect(mediaItemsMessage).toEqual(expected);
-});
\ No newline at end of file
+});
+
+test("parse direct message", () => {
+    const directMessageString = createDirectMessage({
+        id: "test-id-1",
+        createdTime: 12345,
+        to: ["did:example:test2"],
+        from: "did:example:test1",
+        message: "test message"
+    });
+
+    const parsed = parseMessage(directMessageString);
+
+    expect(parsed).toEqual({
+        kind: "direct",
+        id: "test-id-1",
+        createdTime: 12345,
+        to: ["did:example:test2"],
+        from: "did:example:test1",
+        message: "test message"
+    });
+});
+
+test("parse media items message", () => {
+    const mediaItemsMessageString = createMediaItemMessage({
+        id: "test-id-1",
+        mediaItems: [
+            {
+                id: "media-item-id-1",
+                media_type: "application/pdf",
+                link: "ipfs://example-link/test.pdf",
+                hash: "xyz.."
+            },
+            {
+                id: "media-item-id-2",
+                media_type: "text/plain",
+                base64: "dGVzdA=="
+            }
+        ]
+    });
+
+    const parsed = parseMessage(mediaItemsMessageString);
+    if (parsed.kind !== "mediaItems") {
+        throw new Error("expected a media items message");
+    }
+
+    expect(parsed.mediaItems).toEqual([
+        { id: "media-item-id-2", media_type: "text/plain", base64: "dGVzdA==", byte_count: 4 },
+        { id: "media-item-id-1", media_type: "application/pdf", link: "ipfs://example-link/test.pdf", hash: "xyz.." }
+    ]);
+});
+
+test("parse unknown message type", () => {
+    const message = JSON.stringify({
+        id: "test-id-1",
+        typ: "application/didcomm-plain+json",
+        type: "https://didcomm.org/trust-ping/2.0/ping",
+        body: {}
+    });
+
+    expect(() => parseMessage(message)).toThrow();
+});
+
+test("generate key", () => {
+    const key = generateKey("X25519");
+    expect(key.kty).toEqual("OKP");
+    expect(key.crv).toEqual("X25519");
+    expect(key.use).toEqual("enc");
+    expect(key.y).toBeUndefined();
+    expect(key.d).toBeDefined();
+    expect(key.kid).toHaveLength(43);
+});
+
+test("check message freshness", () => {
+    const message = createDirectMessage({
+        createdTime: 12345,
+        expiresTime: 23456,
+        message: "test message"
+    });
+    expect(() => checkFreshness({ message, now: 20000 })).not.toThrow();
+    expect(() => checkFreshness({ message, now: 23456 })).toThrow("Message has expired");
+    expect(() => checkFreshness({ message, now: 23456, skew: 60 })).not.toThrow();
+    expect(() => checkFreshness({ message, now: 12000 })).toThrow("Message is created in the future");
+});
+
+test("split message by recipient", () => {
+    const message = createDirectMessage({
+        id: "test-id-3",
+        to: ["did:example:test2", "did:example:test3", "did:example:test2"],
+        message: "test message"
+    });
+    expect(JSON.parse(message).to).toEqual(["did:example:test2", "did:example:test3"]);
+
+    const copies = splitByRecipient(message).map((copy) => JSON.parse(copy));
+    expect(copies.map((copy) => copy.to)).toEqual([["did:example:test2"], ["did:example:test3"]]);
+    expect(copies.map((copy) => copy.id)).toEqual(["test-id-3", "test-id-3"]);
+});
+
+test("errors carry a code", () => {
+    let error: unknown;
+    try {
+        createDirectMessage({ to: ["not-a-did"], message: "test message" });
+    } catch (e) {
+        error = e;
+    }
+    expect(error).toEqual({
+        code: "INVALID_DID",
+        message: "Failed to build message: Invalid DID: not-a-did"
+    });
+});
+
+test("multihash of media item content", () => {
+    const content = new TextEncoder().encode("hello world");
+    const hash = computeMultihash(content);
+    expect(hash).toEqual("zQmaozNR7DZHQK1ZcU9p7QdrshMvXqWK6gpu5rmrkPdT3L4");
+    expect(verifyHash(content, hash)).toBe(true);
+    expect(verifyHash(new TextEncoder().encode("hello world!"), hash)).toBe(false);
+
+    const hasher = new MultihashHasher("sha3-256");
+    hasher.update(content.subarray(0, 6));
+    hasher.update(content.subarray(6));
+    expect(hasher.finalize()).toEqual(computeMultihash(content, "sha3-256"));
+});
+
+test("encrypt and decrypt media item content", () => {
+    const content = new TextEncoder().encode("hello world");
+    for (const algorithm of [undefined, "XChaCha20-Poly1305"] as const) {
+        const encrypted = encryptContent(content, algorithm);
+        expect(encrypted.ciphering.algorithm).toEqual(algorithm ?? "AES-GCM");
+        expect(encrypted.ciphering.parameters.key).toHaveLength(64);
+        expect(encrypted.ciphertext).toHaveLength(content.length + 16);
+        expect(decryptContent(encrypted.ciphertext, encrypted.ciphering)).toEqual(content);
+
+        const modified = encrypted.ciphertext;
+        modified[0] ^= 1;
+        let error: unknown;
+        try {
+            decryptContent(modified, encrypted.ciphering);
+        } catch (e) {
+            error = e;
+        }
+        expect(error).toMatchObject({ code: "DECRYPTION_FAILED" });
+    }
+});
+
+test("ciphering of media items is validated", () => {
+    const options = (ciphering: any) => ({
+        to: ["did:example:test2"],
+        mediaItems: [
+            {
+                id: "media-item-id-1",
+                media_type: "application/pdf",
+                link: "ipfs://example-link/test.pdf",
+                hash: "xyz..",
+                ciphering
+            }
+        ]
+    });
+
+    let error: unknown;
+    try {
+        createMediaItemMessage(options({ algorithm: "AES-GCM", parameters: { iv: "test-iv", key: "test-key" } }));
+    } catch (e) {
+        error = e;
+    }
+    expect(error).toMatchObject({ code: "INVALID_CIPHERING" });
+
+    const legacy = { algorithm: "aes-256-cbc", parameters: { iv: "2f3849399c60cb04b923bd33265b81c7", key: "test-key" } };
+    const parsed = parseMessage(createMediaItemMessage(options(legacy)));
+    expect(parsed.kind).toEqual("mediaItems");
+    if (parsed.kind === "mediaItems") {
+        expect(parsed.mediaItems[0]).toMatchObject({ ciphering: legacy });
+    }
+});